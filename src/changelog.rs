@@ -1,13 +1,26 @@
 //! Changelog creation logic
 
+mod check;
+mod conventional;
+mod fragment;
 mod parser;
+mod render;
+
+use check::CheckReport;
+use conventional::ConventionalCommit;
 
 use crate::config::Command;
+use crate::config::OutputFormat;
 use crate::git::commit::Commit;
 use crate::git::Git;
+use crate::git::Release;
+use crate::template::find_matching_section;
 use crate::template::ChangelogTemplate;
+use crate::template::CommitStyle;
+use crate::template::Section;
 use crate::template::Template;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::Display;
@@ -22,18 +35,21 @@ pub struct Changelog<'a, T: ChangesList + Default> {
 
 impl<'a, T> Changelog<'a, T>
 where
-    T: ChangesList + Default + Display,
+    T: ChangesList + Default + Display + Clone,
 {
     /// Creates a new [`Changelog`] object. Requires initialized [`Template`] and [`Git`] objects.
     pub fn new(template: &'a mut Template<T>, git: Git) -> Self {
         Self { template, git }
     }
 
-    /// Generates the final changelog markdown string from the commit messages.
+    /// Generates the final changelog from the commit messages, as Markdown or (with
+    /// `format: `[`OutputFormat::Json`]) as a structured JSON document built from the same
+    /// parsed releases/sections/commits the Markdown renderer consumes.
     pub fn generate(
         &mut self,
         project: Option<String>,
         command: Command,
+        format: OutputFormat,
     ) -> Result<String, Box<dyn Error>> {
         let mut project = project;
         let settings = &self.template.settings;
@@ -65,92 +81,750 @@ where
             project = Some(FORCE_CHECK_ALL_PROJECTS.to_string());
         }
 
-        // get prepared general changelog structure from template YAML data
-        let changelog_template = self.template.data();
+        // grab the optional output templates before borrowing `changelog_template` mutably below
+        let header_template = settings.header_template.clone();
+        let body_template = settings.body_template.clone();
+        let footer_template = settings.footer_template.clone();
+        let commit_url_template = settings.commit_url_template();
+        let commit_style = settings.commit_style();
+        let type_map = settings.type_map();
+        let group_by_release = settings.group_by_release();
 
-        let commits = self.git.commits()?;
+        // group commits into per-release buckets (a single `None` "Unreleased" bucket
+        // when tag-aware grouping wasn't requested, same commits as `Git::commits` before)
+        let mut releases = self.git.releases()?;
 
-        // iterate through commits and fill in the changelog_template
-        for commit in commits {
-            // all commit until `since-commit` should belong to `default_project`
-            if use_default_project {
-                if set_default_project {
-                    default_project = default_project_from_config;
-                }
-                if commit.commit_id == projects_since_commit {
-                    set_default_project = true;
+        // merge in fragment-file entries (pending changes authored outside commit messages,
+        // see `fragment`), if configured; they always belong to the "Unreleased" bucket,
+        // which is created if every commit already has a release tag
+        if let Some(fragments_dir) = &settings.fragments_dir {
+            let fragments = fragment::read_fragments(fragments_dir)?;
+            if !fragments.is_empty() {
+                match releases.iter_mut().find(|release| release.version.is_none()) {
+                    Some(release) => release.commits.extend(fragments),
+                    None => releases.insert(
+                        0,
+                        Release {
+                            version: None,
+                            date: None,
+                            commits: fragments,
+                        },
+                    ),
                 }
             }
-
-            let mut commit_changelog = CommitChangelog::new(commit);
-
-            // insert changelog entries from commits to changelog_template
-            commit_changelog.parse(
-                changelog_template,
-                &allowed_projects,
-                &project,
-                default_project,
-            )?;
         }
 
-        // use prepared changelog_template and format the final changelog output
-        let mut buff = String::new();
+        // when checking, collect every commit's parse problem instead of aborting on the
+        // first one, so a single run reports everything a pre-push hook would need fixed
+        let mut check_report = CheckReport::new();
 
         if command == Command::Check {
-            // just checking validity of commits, return empty String
-            return Ok(buff);
-        }
+            // get prepared general changelog structure from template YAML data; release
+            // boundaries don't matter for checking, every commit is validated the same way
+            let changelog_template = self.template.data();
 
-        // prepare and return changelog string
-        buff.push_str("============================================\n\n");
+            for release in releases {
+                for commit in release.commits {
+                    // all commit until `since-commit` should belong to `default_project`
+                    if use_default_project {
+                        if set_default_project {
+                            default_project = default_project_from_config;
+                        }
+                        if commit.commit_id == projects_since_commit {
+                            set_default_project = true;
+                        }
+                    }
 
-        for (_, sec) in changelog_template {
-            if !sec.changes.is_empty() || !sec.subsections.is_empty() {
-                let mut print_section_header = !sec.changes.is_empty();
-                for (_, subsec) in sec.subsections.iter() {
-                    if !subsec.changes.is_empty() {
-                        print_section_header = true;
+                    let mut commit_changelog = CommitChangelog::new(commit);
+                    check_report.record_commit();
+
+                    if let Err(err) = commit_changelog.parse(
+                        changelog_template,
+                        &allowed_projects,
+                        &project,
+                        default_project,
+                        commit_style,
+                        &type_map,
+                    ) {
+                        let commit = &commit_changelog.commit;
+                        let subject = commit.message.lines().next().unwrap_or_default();
+                        check_report.record_issue(commit.commit_id_short(), subject, err);
                     }
                 }
+            }
 
-                if print_section_header {
-                    buff.push_str("## ");
-                    buff.push_str(&sec.title);
-                    buff.push_str("\n\n");
+            if check_report.has_issues() {
+                return Err(check_report.to_string().into());
+            }
 
-                    if !sec.description.is_empty() {
-                        buff.push_str(&sec.description);
-                        buff.push_str("\n\n");
+            // just checking validity of commits, return empty String
+            return Ok(String::new());
+        }
+
+        // fill in one changelog_template per release, each starting from the same pristine
+        // structure, so every release's commits land only in its own copy
+        let template_skeleton = self.template.data().clone();
+        let mut release_templates: Vec<ReleaseTemplate<T>> = Vec::new();
+
+        for release in releases {
+            let mut changelog_template = template_skeleton.clone();
+            let version = release.version;
+            let date = release.date;
+
+            for commit in release.commits {
+                // all commit until `since-commit` should belong to `default_project`
+                if use_default_project {
+                    if set_default_project {
+                        default_project = default_project_from_config;
+                    }
+                    if commit.commit_id == projects_since_commit {
+                        set_default_project = true;
                     }
                 }
+
+                let mut commit_changelog = CommitChangelog::new(commit);
+
+                // insert changelog entries from the commit into this release's changelog_template
+                commit_changelog.parse(
+                    &mut changelog_template,
+                    &allowed_projects,
+                    &project,
+                    default_project,
+                    commit_style,
+                    &type_map,
+                )?;
             }
 
-            if !sec.changes.is_empty() {
-                buff.push_str(&sec.changes.to_string());
+            release_templates.push(ReleaseTemplate {
+                version,
+                date,
+                template: changelog_template,
+            });
+        }
+
+        if format == OutputFormat::Json {
+            let releases = json_releases(&release_templates);
+            return Ok(serde_json::to_string_pretty(&releases)?);
+        }
+
+        // fall back to `DEFAULT_BODY_TEMPLATE` (the historical hard-coded layout, ported to
+        // the same template engine) when no `body-template` is configured, so `generate` is
+        // always a configurable renderer rather than switching between two code paths
+        let body_template = Some(body_template.unwrap_or_else(|| DEFAULT_BODY_TEMPLATE.to_owned()));
+
+        render_templated(
+            &release_templates,
+            header_template,
+            body_template,
+            footer_template,
+            commit_url_template.as_deref(),
+            group_by_release,
+        )
+    }
+
+    /// Splices a freshly generated changelog block above the content of an existing
+    /// changelog file, preserving everything already in `existing` unchanged. Following
+    /// git-cliff's `--prepend`, this lets a rolling `CHANGELOG.md` be updated release by
+    /// release instead of being regenerated from the whole history every time. When a
+    /// `header-template` is configured, it's rendered once and stripped from the front of
+    /// `existing` if already present there, so repeated prepends don't pile up copies of it.
+    pub fn prepend(&self, new_content: &str, existing: &str) -> Result<String, Box<dyn Error>> {
+        prepend(
+            self.template.settings.header_template.as_deref(),
+            new_content,
+            existing,
+        )
+    }
+}
+
+/// Splices `new_content` above `existing`, stripping a leading copy of the rendered
+/// `header_template` from `existing` first (if set) so repeated prepends don't pile up
+/// copies of it. A free function (rather than only a [`Changelog`] method) so [`from_json`]
+/// can reuse it without a [`Changelog`] instance, since it never walks git history.
+pub fn prepend(
+    header_template: Option<&str>,
+    new_content: &str,
+    existing: &str,
+) -> Result<String, Box<dyn Error>> {
+    let header = match header_template {
+        Some(header_template) => Some(render::render(header_template, &render::Context::new())?),
+        None => None,
+    };
+
+    let existing = match &header {
+        Some(header) => existing.strip_prefix(header.as_str()).unwrap_or(existing),
+        None => existing,
+    };
+
+    Ok(format!("{}{}", new_content, existing))
+}
+
+/// One release's filled-in [`ChangelogTemplate`], as built by [`Changelog::generate`]: the
+/// tag (and the date it points at), or `None`/`None` for the "Unreleased" bucket.
+struct ReleaseTemplate<T: ChangesList + Default> {
+    version: Option<String>,
+    date: Option<String>,
+    template: ChangelogTemplate<T>,
+}
+
+/// A release's changelog, in the structured JSON representation ([`OutputFormat::Json`]).
+/// Deserializable so a cached JSON artifact can be read back by [`from_json`] and re-rendered
+/// without re-walking git history.
+#[derive(Serialize, Deserialize)]
+struct JsonRelease {
+    /// `None` for the single "Unreleased" bucket when release grouping wasn't requested.
+    version: Option<String>,
+    /// Date the tag points at, or `None` for the "Unreleased" bucket.
+    date: Option<String>,
+    sections: Vec<JsonSection>,
+}
+
+/// One section (or subsection) of a release's changelog, in JSON form.
+#[derive(Serialize, Deserialize)]
+struct JsonSection {
+    title: String,
+    description: String,
+    commits: Vec<JsonCommit>,
+    subsections: Vec<JsonSection>,
+}
+
+/// One changelog entry, in JSON form. Mirrors [`ChangeEntry`], the same intermediate
+/// representation the Markdown and templated renderers are built from.
+#[derive(Serialize, Deserialize)]
+struct JsonCommit {
+    section: String,
+    title: String,
+    description: String,
+    title_is_enough: bool,
+    /// Same classification [`Changes::add_entry`] uses to decide between a bare bullet and
+    /// a heading-plus-description, kept alongside `title_is_enough` so consumers of the JSON
+    /// don't have to re-derive it.
+    change_type: ChangeType,
+    commit_id: String,
+    commit_id_short: String,
+    author_name: String,
+    author_email: String,
+    date: String,
+}
+
+impl From<&ChangeEntry> for JsonCommit {
+    fn from(entry: &ChangeEntry) -> Self {
+        let change_type = if entry.title_is_enough || entry.description.is_empty() {
+            ChangeType::TitleOnly
+        } else {
+            ChangeType::Other
+        };
+
+        Self {
+            section: entry.section.clone(),
+            title: entry.title.clone(),
+            description: entry.description.clone(),
+            title_is_enough: entry.title_is_enough,
+            change_type,
+            commit_id: entry.commit_id.clone(),
+            commit_id_short: entry.commit_id_short.clone(),
+            author_name: entry.author_name.clone(),
+            author_email: entry.author_email.clone(),
+            date: entry.date.clone(),
+        }
+    }
+}
+
+/// Re-renders a changelog from the JSON produced by a previous [`Changelog::generate`] run
+/// with [`OutputFormat::Json`], without re-walking git history. Lets CI run the (potentially
+/// expensive) git walk once, cache the JSON artifact, and regenerate the human-readable
+/// changelog from it later, or on a machine without access to the repository at all.
+///
+/// `header_template`/`body_template`/`footer_template`/`commit_url_template` and
+/// `group_by_release` mirror the same-named [`Settings`](crate::template::Settings) fields
+/// used by `generate`, so the rendering matches what `generate` would have produced directly.
+pub fn from_json(
+    json: &str,
+    header_template: Option<&str>,
+    body_template: Option<&str>,
+    footer_template: Option<&str>,
+    commit_url_template: Option<&str>,
+    group_by_release: bool,
+) -> Result<String, Box<dyn Error>> {
+    let releases: Vec<JsonRelease> = serde_json::from_str(json)?;
+
+    if let Some(body_template) = body_template {
+        let context = json_render_context(&releases, commit_url_template)?;
+
+        let mut buff = String::new();
+        if let Some(header_template) = header_template {
+            buff.push_str(&render::render(header_template, &context)?);
+        }
+        buff.push_str(&render::render(body_template, &context)?);
+        if let Some(footer_template) = footer_template {
+            buff.push_str(&render::render(footer_template, &context)?);
+        }
+
+        return Ok(buff);
+    }
+
+    let context = json_render_context(&releases, commit_url_template)?;
+
+    let mut buff = String::new();
+    if let Some(header_template) = header_template {
+        buff.push_str(&render::render(header_template, &context)?);
+    }
+    buff.push_str("============================================\n\n");
+
+    for release in &releases {
+        let heading_depth = if group_by_release {
+            buff.push_str("## [");
+            buff.push_str(release.version.as_deref().unwrap_or("Unreleased"));
+            buff.push(']');
+            if let Some(date) = &release.date {
+                buff.push_str(" - ");
+                buff.push_str(date);
             }
+            buff.push_str("\n\n");
+            1
+        } else {
+            0
+        };
 
-            if !sec.subsections.is_empty() {
-                for (_, subsec) in sec.subsections.iter() {
-                    if !subsec.changes.is_empty() {
-                        buff.push_str("### ");
-                        buff.push_str(&subsec.title);
-                        buff.push_str("\n\n");
+        render_json_sections(&mut buff, &release.sections, heading_depth);
+    }
 
-                        if !subsec.description.is_empty() {
-                            buff.push_str(&subsec.description);
-                            buff.push_str("\n\n");
-                        }
-                    }
+    buff.push_str("============================================");
+    if let Some(footer_template) = footer_template {
+        buff.push_str(&render::render(footer_template, &context)?);
+    }
 
-                    buff.push_str(&subsec.changes.to_string());
-                }
+    Ok(buff)
+}
+
+/// Appends `sections`' Markdown rendering to `buff`, the cached-JSON counterpart of
+/// [`DEFAULT_BODY_TEMPLATE`] (which renders straight from a [`ChangelogTemplate`] instead of
+/// from already-flattened JSON). Section filtering (dropping ones with no changes) already
+/// happened when the JSON was produced, so every section here is rendered unconditionally.
+fn render_json_sections(buff: &mut String, sections: &[JsonSection], heading_depth: usize) {
+    let section_heading = "#".repeat(2 + heading_depth);
+    let subsection_heading = "#".repeat(3 + heading_depth);
+
+    for sec in sections {
+        buff.push_str(&section_heading);
+        buff.push(' ');
+        buff.push_str(&sec.title);
+        buff.push_str("\n\n");
+
+        if !sec.description.is_empty() {
+            buff.push_str(&sec.description);
+            buff.push_str("\n\n");
+        }
+
+        // title-only entries first, then the rest, as `Changes`' `Display` orders them
+        let title_only = sec.commits.iter().filter(|c| c.change_type == ChangeType::TitleOnly);
+        let other = sec.commits.iter().filter(|c| c.change_type == ChangeType::Other);
+        for commit in title_only.chain(other) {
+            buff.push_str(&format_json_commit(commit, false));
+        }
+
+        for subsec in &sec.subsections {
+            buff.push_str(&subsection_heading);
+            buff.push(' ');
+            buff.push_str(&subsec.title);
+            buff.push_str("\n\n");
+
+            if !subsec.description.is_empty() {
+                buff.push_str(&subsec.description);
+                buff.push_str("\n\n");
+            }
+
+            let title_only = subsec.commits.iter().filter(|c| c.change_type == ChangeType::TitleOnly);
+            let other = subsec.commits.iter().filter(|c| c.change_type == ChangeType::Other);
+            for commit in title_only.chain(other) {
+                buff.push_str(&format_json_commit(commit, true));
             }
         }
+    }
+}
+
+/// Formats one [`JsonCommit`] the same way [`Changes::add_entry`] formats a live [`ChangeEntry`].
+fn format_json_commit(commit: &JsonCommit, in_subsection: bool) -> String {
+    let mut change = String::new();
+
+    if !commit.title.is_empty() {
+        let title_prefix = match commit.change_type {
+            ChangeType::TitleOnly => "* ",
+            ChangeType::Other if in_subsection => "#### ",
+            ChangeType::Other => "### ",
+        };
+        change.push_str(title_prefix);
+        change.push_str(&commit.title);
+        change.push_str("\n\n");
+    }
+
+    if !commit.description.is_empty() && !commit.title_is_enough {
+        change.push_str(&commit.description);
+        change.push_str("\n\n");
+    }
+
+    change
+}
+
+/// Builds the root [`render::Context`] fed to output templates from cached JSON, the
+/// [`JsonRelease`] counterpart of [`render_context`] (which builds it straight from a
+/// [`ChangelogTemplate`] instead). No filtering is needed here: sections/releases with no
+/// changes were already dropped when the JSON was produced.
+fn json_render_context(
+    releases: &[JsonRelease],
+    commit_url_template: Option<&str>,
+) -> Result<render::Context, Box<dyn Error>> {
+    let mut release_values = vec![];
+
+    for release in releases {
+        let sections = release
+            .sections
+            .iter()
+            .map(|sec| json_section_context(sec, commit_url_template))
+            .collect::<Result<_, Box<dyn Error>>>()?;
+
+        let mut release_ctx = render::Context::new();
+        release_ctx.insert(
+            "version".to_owned(),
+            render::Value::from(release.version.as_deref().unwrap_or("Unreleased")),
+        );
+        release_ctx.insert(
+            "date".to_owned(),
+            render::Value::from(release.date.as_deref().unwrap_or("")),
+        );
+        release_ctx.insert("sections".to_owned(), render::Value::List(sections));
+        release_values.push(render::Value::Object(release_ctx));
+    }
+
+    let mut root = render::Context::new();
+    root.insert("releases".to_owned(), render::Value::List(release_values));
+    Ok(root)
+}
+
+/// Builds the `title`/`description`/`commits`/`subsections` context for one [`JsonSection`],
+/// the JSON counterpart of [`section_context`].
+fn json_section_context(
+    sec: &JsonSection,
+    commit_url_template: Option<&str>,
+) -> Result<render::Value, Box<dyn Error>> {
+    let commits = sec
+        .commits
+        .iter()
+        .map(|commit| json_commit_context(commit, commit_url_template))
+        .collect::<Result<_, Box<dyn Error>>>()?;
+
+    let subsections = sec
+        .subsections
+        .iter()
+        .map(|subsec| json_section_context(subsec, commit_url_template))
+        .collect::<Result<_, Box<dyn Error>>>()?;
 
-        buff.push_str("============================================");
+    let mut ctx = render::Context::new();
+    ctx.insert("title".to_owned(), render::Value::from(sec.title.as_str()));
+    ctx.insert(
+        "description".to_owned(),
+        render::Value::from(sec.description.as_str()),
+    );
+    ctx.insert("commits".to_owned(), render::Value::List(commits));
+    ctx.insert("subsections".to_owned(), render::Value::List(subsections));
+    Ok(render::Value::Object(ctx))
+}
+
+/// Builds the per-commit context for one [`JsonCommit`], the JSON counterpart of the
+/// per-entry context built inline in [`section_context`].
+fn json_commit_context(
+    commit: &JsonCommit,
+    commit_url_template: Option<&str>,
+) -> Result<render::Value, Box<dyn Error>> {
+    let mut c = render::Context::new();
+    c.insert(
+        "section".to_owned(),
+        render::Value::from(commit.section.as_str()),
+    );
+    c.insert("title".to_owned(), render::Value::from(commit.title.as_str()));
+    c.insert(
+        "description".to_owned(),
+        render::Value::from(commit.description.as_str()),
+    );
+    c.insert(
+        "title_is_enough".to_owned(),
+        render::Value::from(commit.title_is_enough),
+    );
+    c.insert("id".to_owned(), render::Value::from(commit.commit_id.as_str()));
+    c.insert(
+        "id_short".to_owned(),
+        render::Value::from(commit.commit_id_short.as_str()),
+    );
+    c.insert(
+        "author_name".to_owned(),
+        render::Value::from(commit.author_name.as_str()),
+    );
+    c.insert(
+        "author_email".to_owned(),
+        render::Value::from(commit.author_email.as_str()),
+    );
+    c.insert("date".to_owned(), render::Value::from(commit.date.as_str()));
 
-        Ok(buff)
+    if let Some(commit_url_template) = commit_url_template {
+        let mut commit_ctx = render::Context::new();
+        commit_ctx.insert("id".to_owned(), render::Value::from(commit.commit_id.as_str()));
+        commit_ctx.insert(
+            "id_short".to_owned(),
+            render::Value::from(commit.commit_id_short.as_str()),
+        );
+        let mut url_root = render::Context::new();
+        url_root.insert("commit".to_owned(), render::Value::Object(commit_ctx));
+        let url = render::render(commit_url_template, &url_root)?;
+        c.insert("url".to_owned(), render::Value::from(url));
     }
+
+    Ok(render::Value::Object(c))
+}
+
+/// Builds the JSON representation of every release, in the same shape (and with the same
+/// "drop releases/sections with no changes" rule) as [`render_context`].
+fn json_releases<T: ChangesList + Default>(release_templates: &[ReleaseTemplate<T>]) -> Vec<JsonRelease> {
+    release_templates
+        .iter()
+        .filter_map(|release| {
+            let sections: Vec<JsonSection> = release.template.values().filter_map(json_section).collect();
+
+            if sections.is_empty() {
+                return None;
+            }
+
+            Some(JsonRelease {
+                version: release.version.clone(),
+                date: release.date.clone(),
+                sections,
+            })
+        })
+        .collect()
+}
+
+/// Builds the JSON representation of one section, or `None` if neither it nor any of its
+/// subsections have changes.
+fn json_section<T: ChangesList + Default>(sec: &Section<T>) -> Option<JsonSection> {
+    let subsections: Vec<JsonSection> = sec.subsections.values().filter_map(json_section).collect();
+
+    if sec.changes.is_empty() && subsections.is_empty() {
+        return None;
+    }
+
+    Some(JsonSection {
+        title: sec.title.clone(),
+        description: sec.description.clone(),
+        commits: sec.changes.entries().iter().map(JsonCommit::from).collect(),
+        subsections,
+    })
+}
+
+/// The `body-template` used when no [`Settings::body_template`](crate::template::Settings)
+/// is configured, reproducing `generate`'s historical hard-coded Markdown layout through the
+/// same template engine every other `body-template` runs through, rather than as a separate
+/// code path. Relies on [`render_context`] pre-computing `heading_prefix` (the templating
+/// engine has no arithmetic, so the release-grouping-dependent heading depth can't be
+/// computed inline) and `changes_text` (the exact bullet/heading formatting
+/// [`Changes::add_entry`] already produces per section).
+const DEFAULT_BODY_TEMPLATE: &str = "\
+============================================
+
+{% for release in releases %}{% if release.grouped %}## [{{ release.version }}]{% if release.date %} - {{ release.date }}{% endif %}
+
+{% endif %}{% for section in release.sections %}{{ section.heading_prefix }} {{ section.title }}
+
+{% if section.description %}{{ section.description }}
+
+{% endif %}{{ section.changes_text }}{% for subsection in section.subsections %}{% if subsection.commits %}{{ subsection.heading_prefix }} {{ subsection.title }}
+
+{% if subsection.description %}{{ subsection.description }}
+
+{% endif %}{% endif %}{{ subsection.changes_text }}{% endfor %}{% endfor %}{% endfor %}\
+============================================";
+
+/// Renders the changelog using the configured output templates: `header_template` and
+/// `footer_template` are each rendered once (if set), and `body_template` is rendered
+/// against a [`render::Context`] exposing a `releases` list (one `None`-versioned
+/// "Unreleased" entry when release grouping wasn't requested), each with `version` and a
+/// `sections` list (`title`, `description`, `commits`, and nested `subsections`) so it can
+/// loop over them with `{% for %}`.
+fn render_templated<T: ChangesList + Default + Display>(
+    release_templates: &[ReleaseTemplate<T>],
+    header_template: Option<String>,
+    body_template: Option<String>,
+    footer_template: Option<String>,
+    commit_url_template: Option<&str>,
+    group_by_release: bool,
+) -> Result<String, Box<dyn Error>> {
+    let context = render_context(release_templates, commit_url_template, group_by_release)?;
+
+    let mut buff = String::new();
+
+    if let Some(header_template) = header_template {
+        buff.push_str(&render::render(&header_template, &context)?);
+    }
+
+    let body_template = body_template.expect("caller only invokes this with a body template set");
+    buff.push_str(&render::render(&body_template, &context)?);
+
+    if let Some(footer_template) = footer_template {
+        buff.push_str(&render::render(&footer_template, &context)?);
+    }
+
+    Ok(buff)
+}
+
+/// Builds the root [`render::Context`] fed to output templates: a `releases` list, each
+/// with `version` (`"Unreleased"` for the `None` bucket), `grouped` (`group_by_release`,
+/// so [`DEFAULT_BODY_TEMPLATE`] only prints a release heading when grouping was actually
+/// requested), and a `sections` list containing only sections with at least one change (own
+/// or in a subsection), so a section is listed when it or one of its subsections has changes.
+/// Releases with no changes anywhere are dropped.
+fn render_context<T: ChangesList + Default + Display>(
+    release_templates: &[ReleaseTemplate<T>],
+    commit_url_template: Option<&str>,
+    group_by_release: bool,
+) -> Result<render::Context, Box<dyn Error>> {
+    // sections nest one level deeper than usual when grouped under a "## [<version>]"
+    // release heading, same as the depth `DEFAULT_BODY_TEMPLATE` replaces
+    let heading_depth = if group_by_release { 1 } else { 0 };
+    let mut releases = vec![];
+
+    for release in release_templates {
+        let mut sections = vec![];
+
+        for (_, sec) in &release.template {
+            let mut subsections = vec![];
+            let mut has_changes = !sec.changes.is_empty();
+
+            for (_, subsec) in sec.subsections.iter() {
+                has_changes |= !subsec.changes.is_empty();
+                subsections.push(render::Value::Object(section_context(
+                    subsec,
+                    heading_depth + 1,
+                    commit_url_template,
+                )?));
+            }
+
+            if !has_changes {
+                continue;
+            }
+
+            let mut ctx = section_context(sec, heading_depth, commit_url_template)?;
+            ctx.insert("subsections".to_owned(), render::Value::List(subsections));
+            sections.push(render::Value::Object(ctx));
+        }
+
+        if sections.is_empty() {
+            continue;
+        }
+
+        let mut release_ctx = render::Context::new();
+        release_ctx.insert(
+            "version".to_owned(),
+            render::Value::from(release.version.as_deref().unwrap_or("Unreleased")),
+        );
+        release_ctx.insert(
+            "date".to_owned(),
+            render::Value::from(release.date.as_deref().unwrap_or("")),
+        );
+        release_ctx.insert("grouped".to_owned(), render::Value::from(group_by_release));
+        release_ctx.insert("sections".to_owned(), render::Value::List(sections));
+        releases.push(render::Value::Object(release_ctx));
+    }
+
+    let mut root = render::Context::new();
+    root.insert("releases".to_owned(), render::Value::List(releases));
+    Ok(root)
+}
+
+/// Builds the `title`/`description`/`commits` context for one section or subsection.
+/// Each commit also exposes `id`/`id_short`/`author_name`/`author_email`/`date`, and a
+/// `url` rendered from `commit_url_template` (e.g. a git-cliff-style commit link) when set.
+/// Also includes two fields only [`DEFAULT_BODY_TEMPLATE`] uses: `heading_prefix` (`##`/`###`
+/// and one level deeper for subsections, offset by `depth`) and `changes_text` (this
+/// section's own changes, pre-formatted by [`Changes::add_entry`]).
+fn section_context<T: ChangesList + Default + Display>(
+    sec: &Section<T>,
+    depth: usize,
+    commit_url_template: Option<&str>,
+) -> Result<render::Context, Box<dyn Error>> {
+    let commits = sec
+        .changes
+        .entries()
+        .iter()
+        .map(|entry| {
+            let mut c = render::Context::new();
+            c.insert(
+                "section".to_owned(),
+                render::Value::from(entry.section.as_str()),
+            );
+            c.insert(
+                "title".to_owned(),
+                render::Value::from(entry.title.as_str()),
+            );
+            c.insert(
+                "description".to_owned(),
+                render::Value::from(entry.description.as_str()),
+            );
+            c.insert(
+                "title_is_enough".to_owned(),
+                render::Value::from(entry.title_is_enough),
+            );
+            c.insert(
+                "id".to_owned(),
+                render::Value::from(entry.commit_id.as_str()),
+            );
+            c.insert(
+                "id_short".to_owned(),
+                render::Value::from(entry.commit_id_short.as_str()),
+            );
+            c.insert(
+                "author_name".to_owned(),
+                render::Value::from(entry.author_name.as_str()),
+            );
+            c.insert(
+                "author_email".to_owned(),
+                render::Value::from(entry.author_email.as_str()),
+            );
+            c.insert("date".to_owned(), render::Value::from(entry.date.as_str()));
+
+            if let Some(commit_url_template) = commit_url_template {
+                let mut commit_ctx = render::Context::new();
+                commit_ctx.insert(
+                    "id".to_owned(),
+                    render::Value::from(entry.commit_id.as_str()),
+                );
+                commit_ctx.insert(
+                    "id_short".to_owned(),
+                    render::Value::from(entry.commit_id_short.as_str()),
+                );
+                let mut url_root = render::Context::new();
+                url_root.insert("commit".to_owned(), render::Value::Object(commit_ctx));
+                let url = render::render(commit_url_template, &url_root)?;
+                c.insert("url".to_owned(), render::Value::from(url));
+            }
+
+            Ok(render::Value::Object(c))
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+
+    let mut ctx = render::Context::new();
+    ctx.insert("title".to_owned(), render::Value::from(sec.title.as_str()));
+    ctx.insert(
+        "description".to_owned(),
+        render::Value::from(sec.description.as_str()),
+    );
+    ctx.insert("commits".to_owned(), render::Value::List(commits));
+    ctx.insert(
+        "heading_prefix".to_owned(),
+        render::Value::from("#".repeat(2 + depth)),
+    );
+    ctx.insert(
+        "changes_text".to_owned(),
+        render::Value::from(sec.changes.to_string()),
+    );
+    Ok(ctx)
 }
 
 /// Changelog information provided in the commit message
@@ -171,17 +845,35 @@ impl CommitChangelog {
         allowed_projects: &[&str],
         project: &Option<String>,
         default_project: &Option<String>,
+        commit_style: CommitStyle,
+        type_map: &HashMap<String, String>,
     ) -> Result<(), Box<dyn Error>>
     where
         T: ChangesList + Default,
     {
-        // parse YAML changelog message
-        let changelog = parser::parse(&self.commit.changelog_message).map_err(|err| {
-            format!(
-                "{} in changelog message in commit:\n>>> {}",
-                err, self.commit.raw_data
-            )
-        })?;
+        let changelog = match commit_style {
+            // a commit that carries a `changelog:` key is parsed from it in either style
+            CommitStyle::Conventional if self.commit.changelog_message.is_empty() => {
+                self.parse_conventional(changelog_template, type_map)?
+            }
+            CommitStyle::Trailer | CommitStyle::Conventional => {
+                if self.commit.changelog_message.is_empty() {
+                    return Err(format!(
+                        "Missing 'changelog:' key in commit:\n>>> {}",
+                        self.commit.raw_data
+                    )
+                    .into());
+                }
+
+                // parse YAML changelog message
+                parser::parse(&self.commit.changelog_message).map_err(|err| {
+                    format!(
+                        "{} in changelog message in commit:\n>>> {}",
+                        err, self.commit.raw_data
+                    )
+                })?
+            }
+        };
 
         if changelog.skip {
             return Ok(());
@@ -294,29 +986,31 @@ impl CommitChangelog {
                 }
             }
 
-            // we have title and description, we can insert them to changelog_template
-            let title_prefix: &str;
-            let mut change_type = ChangeType::Other;
-            let mut change = String::new();
-
-            if !title.is_empty() {
-                if title_is_enough || description.is_empty() {
-                    change_type = ChangeType::TitleOnly;
-                    title_prefix = "* ";
-                } else if !sub_section.is_empty() {
-                    title_prefix = "#### ";
-                } else {
-                    title_prefix = "### ";
-                }
-                change = title_prefix.to_owned();
-                change.push_str(title);
-                change.push_str("\n\n");
+            if title.is_empty() {
+                return Err(format!(
+                    "Missing title in changelog message in commit:\n>>> {}",
+                    self.commit.raw_data
+                )
+                .into());
             }
 
-            if !description.is_empty() && !title_is_enough {
-                change.push_str(description);
-                change.push_str("\n\n");
-            }
+            // we have title and description, we can insert them to changelog_template
+            let entry = ChangeEntry {
+                section: if sub_section.is_empty() {
+                    section.to_owned()
+                } else {
+                    format!("{}.{}", section, sub_section)
+                },
+                title: title.to_owned(),
+                description: description.to_owned(),
+                title_is_enough,
+                in_subsection: !sub_section.is_empty(),
+                commit_id: self.commit.commit_id.clone(),
+                commit_id_short: self.commit.commit_id_short().to_owned(),
+                author_name: self.commit.author_name.clone(),
+                author_email: self.commit.author_email.clone(),
+                date: self.commit.date.clone(),
+            };
 
             if !sub_section.is_empty() {
                 changelog_template
@@ -326,22 +1020,81 @@ impl CommitChangelog {
                     .get_mut(sub_section)
                     .expect("sub_section is not empty here")
                     .changes
-                    .add(change_type, change);
+                    .add_entry(entry);
             } else {
                 changelog_template
                     .get_mut(section)
                     .expect("section should be set correctly")
                     .changes
-                    .add(change_type, change);
+                    .add_entry(entry);
             }
         }
 
         Ok(())
     }
+
+    /// Derives a [`parser::Changelog`] from the commit message parsed as a Conventional Commit,
+    /// for `CommitStyle::Conventional`. The type/scope/subject/breaking-change flag are
+    /// classified against `changelog_template`'s `match:` rules (a section with
+    /// `match: { breaking: true }` claims every breaking change ahead of its type), falling
+    /// back to `type_map`; the scope (if any) becomes the project selector, and the rest of
+    /// the commit message becomes the description.
+    fn parse_conventional<T>(
+        &self,
+        changelog_template: &ChangelogTemplate<T>,
+        type_map: &HashMap<String, String>,
+    ) -> Result<parser::Changelog, Box<dyn Error>>
+    where
+        T: ChangesList + Default,
+    {
+        let cc = ConventionalCommit::parse(&self.commit.message).ok_or_else(|| {
+            format!(
+                "Commit message is not a Conventional Commit:\n>>> {}",
+                self.commit.raw_data
+            )
+        })?;
+
+        let subject = self.commit.message.lines().next().unwrap_or_default();
+        let section = find_matching_section(
+            changelog_template,
+            "",
+            Some(&cc.commit_type),
+            cc.scope.as_deref(),
+            subject,
+            cc.breaking,
+        )
+        .or_else(|| {
+            type_map
+                .get(&cc.commit_type)
+                .filter(|name| changelog_template.contains_key(name.as_str()))
+                .cloned()
+        })
+        .ok_or_else(|| {
+            format!(
+                "Conventional Commit type '{}' is not mapped to any section in commit:\n>>> {}",
+                cc.commit_type, self.commit.raw_data
+            )
+        })?;
+
+        let re = Regex::new(r"\n\s*\n").expect("should never panic"); // description is separated by an empty line
+        let description = re
+            .splitn(&self.commit.message, 2)
+            .nth(1)
+            .map(|body| body.lines().map(str::trim).collect::<Vec<_>>().join(" "))
+            .filter(|description| !description.is_empty());
+
+        Ok(parser::Changelog {
+            project: cc.scope,
+            section,
+            title: Some(cc.description),
+            description,
+            ..Default::default()
+        })
+    }
 }
 
 /// Type of the changelog item
-#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+#[derive(Hash, PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub enum ChangeType {
     /// Changelog item with title only
     TitleOnly,
@@ -349,10 +1102,32 @@ pub enum ChangeType {
     Other,
 }
 
+/// A single changelog item, in the structured form needed by [`render`] templates
+/// (`section`/`title`/`description`/`title_is_enough`) before it is formatted as Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeEntry {
+    /// Full dotted section path the entry was filed under (e.g. `security.vuln_fixes`).
+    pub section: String,
+    pub title: String,
+    pub description: String,
+    pub title_is_enough: bool,
+    /// Whether this entry belongs to a subsection, which renders at one heading level deeper.
+    in_subsection: bool,
+    /// Full 40-char hash of the commit this entry came from.
+    pub commit_id: String,
+    /// Abbreviated (7-char) form of `commit_id`.
+    pub commit_id_short: String,
+    pub author_name: String,
+    pub author_email: String,
+    /// Commit date, as git prints it.
+    pub date: String,
+}
+
 /// List of changelog items in one section
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Changes {
     pub changes: HashMap<ChangeType, Vec<String>>,
+    entries: Vec<ChangeEntry>,
 }
 
 impl Changes {
@@ -360,24 +1135,54 @@ impl Changes {
     fn new() -> Self {
         Self {
             changes: HashMap::from([(ChangeType::TitleOnly, vec![]), (ChangeType::Other, vec![])]),
+            entries: vec![],
         }
     }
 }
 
 pub trait ChangesList {
-    /// Adds new item to the list of changes.
-    fn add(&mut self, change_type: ChangeType, content: String);
+    /// Adds a new changelog entry to the list of changes.
+    fn add_entry(&mut self, entry: ChangeEntry);
 
     /// Returns `true` if the list of changes contains no elements.
     fn is_empty(&self) -> bool;
+
+    /// Returns the raw changelog entries, for template-driven rendering.
+    fn entries(&self) -> &[ChangeEntry];
 }
 
 impl ChangesList for Changes {
-    /// Adds new item to the list of changes.
-    fn add(&mut self, change_type: ChangeType, content: String) {
-        if let Some(v) = self.changes.get_mut(&change_type) {
-            v.push(content);
+    /// Adds a new changelog entry, formatting it into the Markdown layout that
+    /// [`DEFAULT_BODY_TEMPLATE`] prints via `changes_text` and recording its raw fields for templates.
+    fn add_entry(&mut self, entry: ChangeEntry) {
+        let change_type = if entry.title_is_enough || entry.description.is_empty() {
+            ChangeType::TitleOnly
+        } else {
+            ChangeType::Other
         };
+
+        let mut change = String::new();
+        if !entry.title.is_empty() {
+            let title_prefix = match change_type {
+                ChangeType::TitleOnly => "* ",
+                ChangeType::Other if entry.in_subsection => "#### ",
+                ChangeType::Other => "### ",
+            };
+            change.push_str(title_prefix);
+            change.push_str(&entry.title);
+            change.push_str("\n\n");
+        }
+
+        if !entry.description.is_empty() && !entry.title_is_enough {
+            change.push_str(&entry.description);
+            change.push_str("\n\n");
+        }
+
+        if let Some(v) = self.changes.get_mut(&change_type) {
+            v.push(change);
+        }
+
+        self.entries.push(entry);
     }
 
     /// Returns `true` if the list of changes contains no elements.
@@ -392,6 +1197,10 @@ impl ChangesList for Changes {
                 .expect("HashMap has all keys initialized")
                 .is_empty()
     }
+
+    fn entries(&self) -> &[ChangeEntry] {
+        &self.entries
+    }
 }
 
 impl Default for Changes {
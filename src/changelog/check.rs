@@ -0,0 +1,94 @@
+//! Lint-style reporting for `check` command runs: instead of aborting on the first commit
+//! whose changelog message can't be parsed, collect every problem and report them together.
+//! This mirrors GCC's `changelog.py`, which tolerates a wide variety of commit shapes and
+//! tells you precisely which ones it couldn't process, rather than stopping at the first one.
+
+use std::fmt;
+
+/// One commit whose changelog message could not be processed, and why.
+#[derive(Debug)]
+struct Issue {
+    commit_id_short: String,
+    subject: String,
+    reason: String,
+}
+
+/// Accumulates parse problems across all commits seen during a `check` run.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    total: usize,
+    issues: Vec<Issue>,
+}
+
+impl CheckReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that one more commit was looked at.
+    pub fn record_commit(&mut self) {
+        self.total += 1;
+    }
+
+    /// Records that `commit_id_short`/`subject` could not be processed, for `reason`.
+    pub fn record_issue(&mut self, commit_id_short: &str, subject: &str, reason: impl fmt::Display) {
+        self.issues.push(Issue {
+            commit_id_short: commit_id_short.to_owned(),
+            subject: subject.to_owned(),
+            reason: reason.to_string(),
+        });
+    }
+
+    /// Whether any commit failed to be processed.
+    pub fn has_issues(&self) -> bool {
+        !self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} commits could not be processed:",
+            self.issues.len(),
+            self.total
+        )?;
+
+        for issue in &self.issues {
+            writeln!(f, "- {} {}: {}", issue.commit_id_short, issue.subject, issue.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_without_issues() {
+        let mut report = CheckReport::new();
+        report.record_commit();
+        report.record_commit();
+
+        assert!(!report.has_issues());
+        assert_eq!(report.to_string(), "0 of 2 commits could not be processed:\n");
+    }
+
+    #[test]
+    fn report_with_issues() {
+        let mut report = CheckReport::new();
+        report.record_commit();
+        report.record_commit();
+        report.record_commit();
+        report.record_issue("7c85bee", "Fix the thing", "Unknown section 'bogus'");
+
+        assert!(report.has_issues());
+        assert_eq!(
+            report.to_string(),
+            "1 of 3 commits could not be processed:\n- 7c85bee Fix the thing: Unknown section 'bogus'\n"
+        );
+    }
+}
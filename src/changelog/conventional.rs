@@ -0,0 +1,85 @@
+//! Conventional Commits (<https://www.conventionalcommits.org>) subject-line parsing, used
+//! as an alternative to the `changelog:` trailer when `commit-style: conventional` is set.
+
+use regex::Regex;
+
+/// A commit message parsed as a Conventional Commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalCommit {
+    /// The commit type, e.g. `feat`, `fix`, lower-cased.
+    pub commit_type: String,
+    /// The optional `(scope)`, used as the project selector in a multi-project repository.
+    pub scope: Option<String>,
+    /// Whether the subject carried a `!` before the `:`, or the body a `BREAKING CHANGE:`/
+    /// `BREAKING-CHANGE:` footer.
+    pub breaking: bool,
+    /// The text after the `type(scope)!: `, used as the changelog entry's title.
+    pub description: String,
+}
+
+impl ConventionalCommit {
+    /// Parses `message`'s first line as `type(scope)!: description`. Returns `None` if the
+    /// subject doesn't follow the Conventional Commits grammar.
+    pub fn parse(message: &str) -> Option<Self> {
+        let subject_re =
+            Regex::new(r"^([a-zA-Z]+)(?:\(([^()]+)\))?(!)?:\s*(.+)$").expect("should never panic");
+        let breaking_footer_re =
+            Regex::new(r"(?m)^BREAKING[ -]CHANGE:").expect("should never panic");
+
+        let subject = message.lines().next().unwrap_or_default();
+        let caps = subject_re.captures(subject)?;
+
+        Some(Self {
+            commit_type: caps[1].to_lowercase(),
+            scope: caps.get(2).map(|m| m.as_str().to_owned()),
+            breaking: caps.get(3).is_some() || breaking_footer_re.is_match(message),
+            description: caps[4].trim().to_owned(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_simple_subject() {
+        let commit = ConventionalCommit::parse("feat: add thing\n\nSome body text.").unwrap();
+
+        assert_eq!(commit.commit_type, "feat");
+        assert_eq!(commit.scope, None);
+        assert!(!commit.breaking);
+        assert_eq!(commit.description, "add thing");
+    }
+
+    #[test]
+    fn parse_subject_with_scope_and_breaking_bang() {
+        let commit = ConventionalCommit::parse("fix(core)!: drop legacy field").unwrap();
+
+        assert_eq!(commit.commit_type, "fix");
+        assert_eq!(commit.scope.as_deref(), Some("core"));
+        assert!(commit.breaking);
+        assert_eq!(commit.description, "drop legacy field");
+    }
+
+    #[test]
+    fn parse_type_is_case_insensitive() {
+        let commit = ConventionalCommit::parse("Feat: add thing").unwrap();
+        assert_eq!(commit.commit_type, "feat");
+    }
+
+    #[test]
+    fn parse_breaking_change_footer_without_bang() {
+        let commit = ConventionalCommit::parse(
+            "feat(api): add endpoint\n\nBREAKING CHANGE: removes the old endpoint",
+        )
+        .unwrap();
+
+        assert!(commit.breaking);
+    }
+
+    #[test]
+    fn parse_rejects_non_conventional_subject() {
+        assert_eq!(ConventionalCommit::parse("Don't reallocate the buffer"), None);
+    }
+}
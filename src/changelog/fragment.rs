@@ -0,0 +1,126 @@
+//! Fragment-file changelog entries: an alternative to the `changelog:` commit trailer, one
+//! YAML/JSON file per unreleased change in a configured directory (e.g. `.changelog/`).
+//! Avoids merge/rebase conflicts on a shared changelog during parallel feature development,
+//! following the same idea as towncrier's/changesets' "news fragment" directories.
+
+use super::parser;
+use crate::git::commit::Commit;
+use std::error::Error;
+use std::path::Path;
+
+/// Reads every fragment file directly inside `dir` (non-recursively, one change per file) and
+/// turns each into a synthetic [`Commit`] whose `changelog_message` is the fragment's raw
+/// content, so it flows through the same [`super::CommitChangelog::parse`] pipeline as a
+/// commit's `changelog:` trailer (same keys: `project`, `section`, `title`, `description`,
+/// `title-is-enough`, ...). A missing directory yields no fragments, since most repositories
+/// won't use this feature.
+///
+/// Fragments are sorted deterministically by `(section, title)` rather than by filename or
+/// filesystem enumeration order, so the generated changelog doesn't depend on the order authors
+/// happened to create their files in.
+pub fn read_fragments(dir: &Path) -> Result<Vec<Commit>, Box<dyn Error>> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Ok(vec![]);
+    };
+
+    let mut fragments = vec![];
+
+    for entry in read_dir {
+        let entry = entry.map_err(|err| {
+            format!(
+                "Failed to read fragments directory '{}': {}",
+                dir.display(),
+                err
+            )
+        })?;
+        let path = entry.path();
+
+        if !path.is_file() {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&path).map_err(|err| {
+            format!("Failed to read fragment file '{}': {}", path.display(), err)
+        })?;
+
+        let commit_id = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .to_owned();
+
+        fragments.push(Commit {
+            commit_id,
+            header: format!("fragment {}", path.display()),
+            message: String::new(),
+            changelog_message: contents,
+            raw_data: format!("fragment file '{}'", path.display()),
+            author_name: String::new(),
+            author_email: String::new(),
+            date: String::new(),
+        });
+    }
+
+    fragments.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+
+    Ok(fragments)
+}
+
+/// `(section, title)` of a fragment, for deterministic ordering. A fragment that fails to
+/// parse sorts by its commit id (i.e. its file name) instead, since its real parse error is
+/// reported later by `CommitChangelog::parse`.
+fn sort_key(commit: &Commit) -> (String, String) {
+    match parser::parse(&commit.changelog_message) {
+        Ok(changelog) => (changelog.section, changelog.title.unwrap_or_default()),
+        Err(_) => (commit.commit_id.clone(), String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "mkchlog_fragment_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn read_fragments_returns_empty_for_missing_directory() {
+        let dir = std::env::temp_dir().join("mkchlog_fragment_test_does_not_exist");
+        assert!(read_fragments(&dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_fragments_sorts_by_section_then_title() {
+        let dir = scratch_dir("sorts_by_section_then_title");
+
+        std::fs::write(
+            dir.join("a.yml"),
+            "section: features\ntitle: Z feature\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("b.yml"), "section: bugfixes\ntitle: A fix\n").unwrap();
+        std::fs::write(
+            dir.join("c.yml"),
+            "section: features\ntitle: A feature\n",
+        )
+        .unwrap();
+
+        let fragments = read_fragments(&dir).unwrap();
+        let titles: Vec<String> = fragments
+            .iter()
+            .map(|c| parser::parse(&c.changelog_message).unwrap().title.unwrap())
+            .collect();
+
+        assert_eq!(titles, vec!["A fix", "A feature", "Z feature"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,19 +1,52 @@
 //! YAML parser
 
-use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 use serde_yaml::Error;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::marker::PhantomData;
 use std::str::FromStr;
 
-/// Parse the content of a changelog message into a [`Changelog`] structure
+/// Highest changelog message-format version this build of mkchlog understands. A commit may
+/// declare an older or equal `version:`, which is accepted as-is; declaring a newer one is
+/// rejected by [`parse`] with an actionable error instead of a confusing unknown-field one.
+const SUPPORTED_FORMAT_VERSION: u32 = 1;
+
+/// The `version:` a commit is assumed to target when it doesn't declare one, i.e. the format
+/// understood before the `version` key was introduced.
+fn default_format_version() -> u32 {
+    SUPPORTED_FORMAT_VERSION
+}
+
+/// Parse the content of a changelog message into a [`Changelog`] structure. Rejects any key
+/// this version of mkchlog doesn't recognize; see [`parse_lenient`] for a forward-compatible
+/// alternative.
 pub fn parse(s: &str) -> Result<Changelog, Error> {
     let s = &format!("changelog:{}", s);
     let chw = serde_yaml::from_str::<ChangelogWrapper>(s)?;
+
+    if chw.changelog.version > SUPPORTED_FORMAT_VERSION {
+        return Err(de::Error::custom(format!(
+            "commit declares changelog format v{} but this mkchlog supports up to v{}",
+            chw.changelog.version, SUPPORTED_FORMAT_VERSION
+        )));
+    }
+
     Ok(chw.changelog)
 }
 
+/// Like [`parse`], but tolerates keys this version of mkchlog doesn't recognize instead of
+/// failing outright, e.g. a commit authored against a newer mkchlog config that added a field
+/// this version doesn't know about yet. Unrecognized keys are returned alongside the parsed
+/// [`Changelog`] (sorted, deduplicated) so the caller can warn about them instead of rejecting
+/// the whole commit.
+pub fn parse_lenient(s: &str) -> Result<(Changelog, Vec<String>), Error> {
+    let s = &format!("changelog:{}", s);
+    let chw = serde_yaml::from_str::<LenientChangelogWrapper>(s)?;
+    Ok(chw.changelog.into_parts())
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 struct ChangelogWrapper {
@@ -32,9 +65,149 @@ pub struct Changelog {
     #[serde(rename = "title-is-enough", default)]
     pub title_is_enough: bool,
     pub description: Option<String>,
-    pub inherit: Option<String>, // ignored, only for backwards compatibility
-    #[serde(skip)]
+    /// Name of a template (from the set passed to [`Changelog::resolve_inherit`]) whose
+    /// `section`/`title`/`description`/`title-is-enough` are merged in for any field this
+    /// entry leaves unset.
+    pub inherit: Option<String>,
+    /// Per-project overrides for a commit that affects several projects in the same section.
+    /// Populated either from the top-level list form (a bare sequence of `project:` maps) or
+    /// from this combined form (a map with a `projects:` key); in the latter case each
+    /// [`Project`] that leaves `section`/`title`/`description`/`title-is-enough` unset inherits
+    /// the shared value from this [`Changelog`], folded in by
+    /// [`WithProjects::fold_shared_project_defaults`].
+    #[serde(default)]
     pub projects: Option<Vec<Project>>,
+    /// Changelog message-format version this commit was written for. Defaults to
+    /// [`SUPPORTED_FORMAT_VERSION`] when absent, so existing commits keep parsing unchanged.
+    #[serde(default = "default_format_version")]
+    pub version: u32,
+    /// Change-type/severity classification carried by a YAML `!Tag` (`!breaking`,
+    /// `!security`, `!deprecation`, `!normal`) on the changelog message, if any. Set by the
+    /// custom `string_or_struct_or_vec` deserializer; never read directly as a YAML key.
+    #[serde(skip)]
+    pub change_type: Option<ChangeType>,
+}
+
+/// Change-type/severity classification carried by a YAML `!Tag` on a changelog message, e.g.
+/// `changelog: !breaking { section: api, title: ... }`. Lets downstream formatting group or
+/// highlight breaking/security changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeType {
+    Breaking,
+    Security,
+    Deprecation,
+    Normal,
+}
+
+impl ChangeType {
+    /// Maps a YAML tag name (without the leading `!`) to a [`ChangeType`], if recognized.
+    fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "breaking" => Some(ChangeType::Breaking),
+            "security" => Some(ChangeType::Security),
+            "deprecation" => Some(ChangeType::Deprecation),
+            "normal" => Some(ChangeType::Normal),
+            _ => None,
+        }
+    }
+}
+
+/// Lets `string_or_struct_or_vec` attach the `!Tag`-derived [`ChangeType`] (if any) to the
+/// value it produces, without hardcoding the deserializer to [`Changelog`] specifically.
+trait WithChangeType {
+    fn set_change_type(&mut self, change_type: ChangeType);
+}
+
+impl WithChangeType for Changelog {
+    fn set_change_type(&mut self, change_type: ChangeType) {
+        self.change_type = Some(change_type);
+    }
+}
+
+/// Lets `string_or_struct_or_vec` fold a map's shared `section`/`title`/`description`/
+/// `title-is-enough` into any `projects:` it also carries (the combined form), without
+/// hardcoding the deserializer to [`Changelog`] specifically.
+trait WithProjects {
+    fn fold_shared_project_defaults(&mut self);
+}
+
+impl WithProjects for Changelog {
+    fn fold_shared_project_defaults(&mut self) {
+        if let Some(projects) = self.projects.take() {
+            let projects = projects
+                .into_iter()
+                .map(|project| Project {
+                    section: project.section.or_else(|| Some(self.section.clone())),
+                    title: project.title.or_else(|| self.title.clone()),
+                    title_is_enough: project.title_is_enough || self.title_is_enough,
+                    description: project.description.or_else(|| self.description.clone()),
+                    ..project
+                })
+                .collect();
+
+            self.projects = Some(projects);
+        }
+    }
+}
+
+impl Changelog {
+    /// Resolves `inherit` against `templates`: fills any `section`/`title`/`description`/
+    /// `title-is-enough` this entry (and any of its `projects`) leaves unset from the named
+    /// template, following the template's own `inherit` chain as well. Errors if `inherit`
+    /// names a template missing from `templates`, or if the chain revisits a template already
+    /// seen (an inheritance cycle).
+    pub fn resolve_inherit(&mut self, templates: &HashMap<String, Changelog>) -> Result<(), Error> {
+        let mut seen = Vec::new();
+        self.resolve_inherit_chain(templates, &mut seen)
+    }
+
+    fn resolve_inherit_chain(
+        &mut self,
+        templates: &HashMap<String, Changelog>,
+        seen: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        let name = match self.inherit.take() {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+
+        if seen.contains(&name) {
+            seen.push(name);
+            return Err(de::Error::custom(format!(
+                "Inheritance cycle detected: {}",
+                seen.join(" -> ")
+            )));
+        }
+
+        let mut template = templates
+            .get(&name)
+            .cloned()
+            .ok_or_else(|| de::Error::custom(format!("Unknown inherited template '{}'", name)))?;
+
+        seen.push(name);
+        template.resolve_inherit_chain(templates, seen)?;
+
+        if self.section.is_empty() {
+            self.section = template.section.clone();
+        }
+        if self.title.is_none() {
+            self.title = template.title.clone();
+        }
+        if self.description.is_none() {
+            self.description = template.description.clone();
+        }
+        if !self.title_is_enough {
+            self.title_is_enough = template.title_is_enough;
+        }
+
+        if let Some(projects) = &mut self.projects {
+            for project in projects {
+                project.merge_from_template(&template);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -56,6 +229,26 @@ pub struct Project {
     pub description: Option<String>,
 }
 
+impl Project {
+    /// Fills any `section`/`title`/`description`/`title-is-enough` this project leaves unset
+    /// from `template`, the already-resolved [`Changelog`] named by the enclosing entry's
+    /// `inherit`. The analogue of [`Changelog::resolve_inherit`] for a per-project override.
+    fn merge_from_template(&mut self, template: &Changelog) {
+        if self.section.is_none() {
+            self.section = Some(template.section.clone()).filter(|section| !section.is_empty());
+        }
+        if self.title.is_none() {
+            self.title = template.title.clone();
+        }
+        if self.description.is_none() {
+            self.description = template.description.clone();
+        }
+        if !self.title_is_enough {
+            self.title_is_enough = template.title_is_enough;
+        }
+    }
+}
+
 impl From<Project> for Changelog {
     fn from(project: Project) -> Self {
         Changelog {
@@ -67,6 +260,8 @@ impl From<Project> for Changelog {
             description: project.description,
             inherit: None,
             projects: None,
+            version: default_format_version(),
+            change_type: None,
         }
     }
 }
@@ -100,7 +295,7 @@ impl FromStr for Changelog {
 // T type can be deserialized either from a string, map or sequence of maps
 fn string_or_struct_or_vec<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
-    T: Deserialize<'de> + FromStr<Err = Error> + From<Vec<Project>>,
+    T: Deserialize<'de> + FromStr<Err = Error> + From<Vec<Project>> + WithChangeType + WithProjects,
     D: Deserializer<'de>,
 {
     // This is a Visitor that forwards string types to T's `FromStr` impl and
@@ -112,7 +307,7 @@ where
 
     impl<'de, T> Visitor<'de> for StringOrStructOrVec<T>
     where
-        T: Deserialize<'de> + FromStr<Err = Error> + From<Vec<Project>>,
+        T: Deserialize<'de> + FromStr<Err = Error> + From<Vec<Project>> + WithChangeType + WithProjects,
     {
         type Value = T;
 
@@ -135,7 +330,9 @@ where
             // into a `Deserializer`, allowing it to be used as the input to T's
             // `Deserialize` implementation. T then deserializes itself using
             // the entries from the map visitor.
-            Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+            let mut value: T = Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))?;
+            value.fold_shared_project_defaults();
+            Ok(value)
         }
 
         fn visit_seq<S>(self, mut seq: S) -> Result<T, S::Error>
@@ -150,11 +347,221 @@ where
 
             Ok(projects.into())
         }
+
+        fn visit_enum<A>(self, data: A) -> Result<T, A::Error>
+        where
+            A: EnumAccess<'de>,
+        {
+            // A `!Tag` node (e.g. `!breaking {...}`): the tag carries the `ChangeType`, and
+            // the tagged payload is deserialized exactly as an untagged node would be, by
+            // feeding it back through this same visitor.
+            let (tag, variant): (String, A::Variant) = data.variant()?;
+
+            let change_type = ChangeType::from_tag(&tag).ok_or_else(|| {
+                de::Error::custom(format!("Unknown changelog tag '!{}'", tag))
+            })?;
+
+            let mut value: T = variant.newtype_variant_seed(self)?;
+            value.set_change_type(change_type);
+
+            Ok(value)
+        }
+    }
+
+    impl<'de, T> DeserializeSeed<'de> for StringOrStructOrVec<T>
+    where
+        T: Deserialize<'de> + FromStr<Err = Error> + From<Vec<Project>> + WithChangeType + WithProjects,
+    {
+        type Value = T;
+
+        fn deserialize<D2>(self, deserializer: D2) -> Result<T, D2::Error>
+        where
+            D2: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(self)
+        }
     }
 
     deserializer.deserialize_any(StringOrStructOrVec(PhantomData))
 }
 
+/// Lenient counterpart of [`ChangelogWrapper`] used by [`parse_lenient`]: no
+/// `deny_unknown_fields`, so unrecognized keys fall through to [`LenientChangelog::unknown`]
+/// instead of failing the parse.
+#[derive(Debug, Deserialize)]
+struct LenientChangelogWrapper {
+    #[serde(deserialize_with = "lenient_string_or_struct_or_vec")]
+    changelog: LenientChangelog,
+}
+
+/// Lenient counterpart of [`Changelog`]: same recognized fields, but unknown keys are
+/// collected into `unknown` instead of causing a parse error. The exception is `projects`,
+/// which is not read from the map form, so a `projects:` key there lands in `unknown`.
+#[derive(Debug, Deserialize, Default)]
+struct LenientChangelog {
+    #[serde(default)]
+    skip: bool,
+    project: Option<String>,
+    section: String,
+    title: Option<String>,
+    #[serde(rename = "title-is-enough", default)]
+    title_is_enough: bool,
+    description: Option<String>,
+    inherit: Option<String>,
+    #[serde(skip)]
+    projects: Option<Vec<LenientProject>>,
+    #[serde(default = "default_format_version")]
+    version: u32,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Lenient counterpart of [`Project`]: same recognized fields, but unknown keys are collected
+/// into `unknown` instead of causing a parse error.
+#[derive(Debug, Deserialize, Default)]
+struct LenientProject {
+    #[serde(default)]
+    skip: bool,
+    name: String,
+    section: Option<String>,
+    title: Option<String>,
+    #[serde(rename = "title-is-enough", default)]
+    title_is_enough: bool,
+    description: Option<String>,
+    #[serde(flatten)]
+    unknown: BTreeMap<String, serde_yaml::Value>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct LenientProjectWrapper {
+    project: LenientProject,
+}
+
+impl LenientProject {
+    /// Splits this [`LenientProject`] into a strict [`Project`] plus the names of any
+    /// unrecognized keys it carried.
+    fn into_parts(self) -> (Project, Vec<String>) {
+        let unknown = self.unknown.into_keys().collect();
+
+        let project = Project {
+            skip: self.skip,
+            name: self.name,
+            section: self.section,
+            title: self.title,
+            title_is_enough: self.title_is_enough,
+            description: self.description,
+        };
+
+        (project, unknown)
+    }
+}
+
+impl LenientChangelog {
+    /// Splits this [`LenientChangelog`] into a strict [`Changelog`] plus the names of any
+    /// unrecognized keys it (or one of its `projects`) carried, sorted and deduplicated.
+    fn into_parts(self) -> (Changelog, Vec<String>) {
+        let mut unknown: Vec<String> = self.unknown.into_keys().collect();
+
+        let projects = self.projects.map(|projects| {
+            projects
+                .into_iter()
+                .map(|project| {
+                    let (project, project_unknown) = project.into_parts();
+                    unknown.extend(project_unknown);
+                    project
+                })
+                .collect()
+        });
+
+        let changelog = Changelog {
+            skip: self.skip,
+            project: self.project,
+            section: self.section,
+            title: self.title,
+            title_is_enough: self.title_is_enough,
+            description: self.description,
+            inherit: self.inherit,
+            projects,
+            version: self.version,
+            change_type: None,
+        };
+
+        unknown.sort();
+        unknown.dedup();
+
+        (changelog, unknown)
+    }
+}
+
+impl FromStr for LenientChangelog {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim() != "skip" {
+            return Err(de::Error::custom(format!("Unexpected value '{}'", s)));
+        }
+
+        Ok(LenientChangelog {
+            skip: true,
+            ..Default::default()
+        })
+    }
+}
+
+impl From<Vec<LenientProject>> for LenientChangelog {
+    fn from(projects: Vec<LenientProject>) -> Self {
+        LenientChangelog {
+            projects: Some(projects),
+            ..Default::default()
+        }
+    }
+}
+
+/// Like `string_or_struct_or_vec`, but for [`LenientChangelog`]/[`LenientProject`] so a
+/// sequence (multi-project) changelog message stays lenient per-project too.
+fn lenient_string_or_struct_or_vec<'de, D>(deserializer: D) -> Result<LenientChangelog, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct LenientStringOrStructOrVec;
+
+    impl<'de> Visitor<'de> for LenientStringOrStructOrVec {
+        type Value = LenientChangelog;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("string or map or sequence")
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<LenientChangelog, E>
+        where
+            E: de::Error,
+        {
+            LenientChangelog::from_str(value).map_err(|err| de::Error::custom(err.to_string()))
+        }
+
+        fn visit_map<M>(self, map: M) -> Result<LenientChangelog, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            Deserialize::deserialize(de::value::MapAccessDeserializer::new(map))
+        }
+
+        fn visit_seq<S>(self, mut seq: S) -> Result<LenientChangelog, S::Error>
+        where
+            S: SeqAccess<'de>,
+        {
+            let mut projects = Vec::<LenientProject>::new();
+            while let Some(pw) = seq.next_element::<LenientProjectWrapper>()? {
+                projects.push(pw.project);
+            }
+
+            Ok(projects.into())
+        }
+    }
+
+    deserializer.deserialize_any(LenientStringOrStructOrVec)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +579,8 @@ mod tests {
             description: None,
             inherit: None,
             projects: None,
+            version: 1,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
@@ -194,6 +603,8 @@ mod tests {
             description: None,
             inherit: None,
             projects: None,
+            version: 1,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
@@ -212,6 +623,8 @@ mod tests {
             description: None,
             inherit: None,
             projects: None,
+            version: 1,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
@@ -230,6 +643,8 @@ mod tests {
             description: None,
             inherit: None,
             projects: None,
+            version: 1,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
@@ -247,6 +662,8 @@ mod tests {
             description: None,
             inherit: None,
             projects: None,
+            version: 1,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
@@ -319,9 +736,346 @@ mod tests {
                     description: None,
                 },
             ]),
+            version: 0,
+            change_type: None,
         };
 
         let res = parse(yaml).unwrap();
         assert_eq!(res, expected);
     }
+
+    #[test]
+    fn parse_changelog_yaml_map_with_projects_inherits_shared_fields() {
+        let yaml = "
+        section: dev
+        title-is-enough: true
+        projects:
+          - name: mkchlog
+          - name: mkchlog-action
+            section: doc
+            title-is-enough: false";
+
+        let expected = Changelog {
+            skip: false,
+            project: None,
+            section: "dev".to_owned(),
+            title: None,
+            title_is_enough: true,
+            description: None,
+            inherit: None,
+            projects: Some(vec![
+                Project {
+                    skip: false,
+                    name: "mkchlog".to_owned(),
+                    section: Some("dev".to_owned()),
+                    title: None,
+                    title_is_enough: true,
+                    description: None,
+                },
+                Project {
+                    skip: false,
+                    name: "mkchlog-action".to_owned(),
+                    section: Some("doc".to_owned()),
+                    title: None,
+                    title_is_enough: false,
+                    description: None,
+                },
+            ]),
+            version: 1,
+            change_type: None,
+        };
+
+        let res = parse(yaml).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn parse_changelog_yaml_tagged_map() {
+        let yaml = "
+        !breaking
+        section: api
+        title: Removed the deprecated 'foo' endpoint";
+
+        let res = parse(yaml).unwrap();
+
+        assert_eq!(res.change_type, Some(ChangeType::Breaking));
+        assert_eq!(res.section, "api");
+        assert_eq!(
+            res.title.as_deref(),
+            Some("Removed the deprecated 'foo' endpoint")
+        );
+    }
+
+    #[test]
+    fn parse_changelog_yaml_tagged_skip() {
+        let yaml = " !normal skip";
+
+        let res = parse(yaml).unwrap();
+
+        assert_eq!(res.change_type, Some(ChangeType::Normal));
+        assert!(res.skip);
+    }
+
+    #[test]
+    fn parse_changelog_yaml_unknown_tag() {
+        let yaml = "
+        !unheard_of
+        section: api";
+
+        let res = parse(yaml);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown changelog tag '!unheard_of'"));
+    }
+
+    #[test]
+    fn parse_changelog_yaml_explicit_supported_version() {
+        let yaml = "
+        version: 1
+        section: features";
+
+        let expected = Changelog {
+            skip: false,
+            project: None,
+            section: "features".to_owned(),
+            title: None,
+            title_is_enough: false,
+            description: None,
+            inherit: None,
+            projects: None,
+            version: 1,
+            change_type: None,
+        };
+
+        let res = parse(yaml).unwrap();
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn parse_changelog_yaml_unsupported_version() {
+        let yaml = "
+        version: 2
+        section: features";
+
+        let res = parse(yaml);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("commit declares changelog format v2 but this mkchlog supports up to v1"));
+    }
+
+    #[test]
+    fn parse_lenient_collects_unknown_field_instead_of_failing() {
+        let yaml = "
+        section: features
+        nonsense: yes";
+
+        let expected = Changelog {
+            skip: false,
+            project: None,
+            section: "features".to_owned(),
+            title: None,
+            title_is_enough: false,
+            description: None,
+            inherit: None,
+            projects: None,
+            version: 1,
+            change_type: None,
+        };
+
+        let (res, unknown) = parse_lenient(yaml).unwrap();
+        assert_eq!(res, expected);
+        assert_eq!(unknown, vec!["nonsense".to_owned()]);
+    }
+
+    #[test]
+    fn parse_lenient_without_unknown_fields_returns_empty_list() {
+        let yaml = "
+        section: features";
+
+        let (_, unknown) = parse_lenient(yaml).unwrap();
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn parse_lenient_collects_unknown_fields_from_each_project() {
+        let yaml = "
+        - project:
+           name: mkchlog
+           section: dev
+           future-key: true
+        - project:
+           name: mkchlog-action
+           skip: true
+           another-future-key: true";
+
+        let (res, unknown) = parse_lenient(yaml).unwrap();
+        assert_eq!(res.projects.unwrap().len(), 2);
+        assert_eq!(
+            unknown,
+            vec!["another-future-key".to_owned(), "future-key".to_owned()]
+        );
+    }
+
+    fn template(section: &str, title: &str, description: &str) -> Changelog {
+        Changelog {
+            section: section.to_owned(),
+            title: Some(title.to_owned()),
+            description: Some(description.to_owned()),
+            title_is_enough: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolve_inherit_fills_unset_fields_from_named_template() {
+        let templates = HashMap::from([(
+            "default-doc".to_owned(),
+            template("doc", "Doc update", "Boilerplate doc description"),
+        )]);
+
+        let mut res = Changelog {
+            inherit: Some("default-doc".to_owned()),
+            ..Default::default()
+        };
+        res.resolve_inherit(&templates).unwrap();
+
+        assert_eq!(res.section, "doc");
+        assert_eq!(res.title.as_deref(), Some("Doc update"));
+        assert_eq!(
+            res.description.as_deref(),
+            Some("Boilerplate doc description")
+        );
+        assert!(res.title_is_enough);
+        assert!(res.inherit.is_none());
+    }
+
+    #[test]
+    fn resolve_inherit_keeps_fields_the_entry_already_set() {
+        let templates = HashMap::from([(
+            "default-doc".to_owned(),
+            template("doc", "Doc update", "Boilerplate doc description"),
+        )]);
+
+        let mut res = Changelog {
+            inherit: Some("default-doc".to_owned()),
+            section: "features".to_owned(),
+            title: Some("A more specific title".to_owned()),
+            ..Default::default()
+        };
+        res.resolve_inherit(&templates).unwrap();
+
+        assert_eq!(res.section, "features");
+        assert_eq!(res.title.as_deref(), Some("A more specific title"));
+        assert_eq!(
+            res.description.as_deref(),
+            Some("Boilerplate doc description")
+        );
+    }
+
+    #[test]
+    fn resolve_inherit_follows_chained_templates() {
+        let templates = HashMap::from([
+            (
+                "base".to_owned(),
+                template("doc", "Doc update", "Base description"),
+            ),
+            (
+                "specific".to_owned(),
+                Changelog {
+                    inherit: Some("base".to_owned()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let mut res = Changelog {
+            inherit: Some("specific".to_owned()),
+            ..Default::default()
+        };
+        res.resolve_inherit(&templates).unwrap();
+
+        assert_eq!(res.section, "doc");
+        assert_eq!(res.title.as_deref(), Some("Doc update"));
+    }
+
+    #[test]
+    fn resolve_inherit_merges_into_each_project() {
+        let templates = HashMap::from([(
+            "default-doc".to_owned(),
+            template("doc", "Doc update", "Boilerplate doc description"),
+        )]);
+
+        let mut res = Changelog {
+            inherit: Some("default-doc".to_owned()),
+            projects: Some(vec![
+                Project {
+                    name: "mkchlog".to_owned(),
+                    ..Default::default()
+                },
+                Project {
+                    name: "mkchlog-action".to_owned(),
+                    section: Some("features".to_owned()),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        };
+        res.resolve_inherit(&templates).unwrap();
+
+        let projects = res.projects.unwrap();
+        assert_eq!(projects[0].section.as_deref(), Some("doc"));
+        assert_eq!(projects[0].title.as_deref(), Some("Doc update"));
+        assert_eq!(projects[1].section.as_deref(), Some("features"));
+        assert_eq!(projects[1].title.as_deref(), Some("Doc update"));
+    }
+
+    #[test]
+    fn resolve_inherit_errors_on_unknown_template() {
+        let templates = HashMap::new();
+
+        let mut res = Changelog {
+            inherit: Some("no-such-template".to_owned()),
+            ..Default::default()
+        };
+        let err = res.resolve_inherit(&templates).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Unknown inherited template 'no-such-template'"
+        );
+    }
+
+    #[test]
+    fn resolve_inherit_errors_on_cycle() {
+        let templates = HashMap::from([
+            (
+                "a".to_owned(),
+                Changelog {
+                    inherit: Some("b".to_owned()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "b".to_owned(),
+                Changelog {
+                    inherit: Some("a".to_owned()),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let mut res = Changelog {
+            inherit: Some("a".to_owned()),
+            ..Default::default()
+        };
+        let err = res.resolve_inherit(&templates).unwrap_err();
+
+        assert_eq!(err.to_string(), "Inheritance cycle detected: a -> b -> a");
+    }
 }
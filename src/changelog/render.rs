@@ -0,0 +1,395 @@
+//! Minimal template engine powering the optional `header-template`/`body-template`/
+//! `footer-template` [`Settings`](crate::template::Settings) fields: variable
+//! substitution (`{{ var }}`), `for` loops over lists (`{% for x in xs %}...{% endfor %}`),
+//! `if` conditionals over booleans/string-emptiness (`{% if cond %}...{% endif %}`), and a
+//! handful of Tera-style filters applied with `{{ var | filter }}`: `upper_first`
+//! (capitalizes the first character) and `trim_start_matches("prefix")` (strips a literal
+//! prefix, a no-op if it isn't present). Filters can be chained, e.g.
+//! `{{ commit.title | trim_start_matches("fix: ") | upper_first }}`.
+//!
+//! This is intentionally not a general-purpose templating language (no expressions,
+//! no user-defined filters) — just enough to let downstream projects match their own
+//! changelog style (bullet lists, tables, commit links) without patching the crate.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// A value bound in a [`Context`]: a scalar, a list to loop over, or a nested object.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Str(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Object(Context),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::List(l) => !l.is_empty(),
+            Value::Object(_) => true,
+        }
+    }
+
+    fn to_display(&self) -> String {
+        match self {
+            Value::Str(s) => s.clone(),
+            Value::Bool(b) => b.to_string(),
+            Value::List(_) | Value::Object(_) => String::new(),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Str(s.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Str(s)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+/// A set of named values available to a template, e.g. one changelog section or commit.
+pub type Context = HashMap<String, Value>;
+
+/// A filter applied to a `{{ var | filter }}` expression, e.g. `trim_start_matches("fix: ")`.
+#[derive(Debug, Clone)]
+struct Filter {
+    name: String,
+    arg: Option<String>,
+}
+
+fn apply_filter(filter: &Filter, value: String) -> String {
+    match filter.name.as_str() {
+        "upper_first" => {
+            let mut chars = value.chars();
+            match chars.next() {
+                Some(c) => c.to_uppercase().chain(chars).collect(),
+                None => value,
+            }
+        }
+        "trim_start_matches" => {
+            let pattern = filter.arg.as_deref().unwrap_or("");
+            value
+                .strip_prefix(pattern)
+                .map(str::to_owned)
+                .unwrap_or(value)
+        }
+        // unknown filters pass the value through unchanged rather than erroring, so
+        // templates stay forward-compatible with filters added in later versions
+        _ => value,
+    }
+}
+
+/// Parses a `| name` / `| name("arg")` filter chain, as captured by [`tokenize`].
+fn parse_filters(raw: &str) -> Vec<Filter> {
+    let re = Regex::new(r#"\|\s*(\w+)(?:\(\s*"([^"]*)"\s*\)|\(\s*'([^']*)'\s*\))?"#)
+        .expect("should never panic");
+
+    re.captures_iter(raw)
+        .map(|cap| Filter {
+            name: cap[1].to_owned(),
+            arg: cap
+                .get(2)
+                .or_else(|| cap.get(3))
+                .map(|m| m.as_str().to_owned()),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Var(String, Vec<Filter>),
+    For {
+        var: String,
+        iter: String,
+        body: Vec<Node>,
+    },
+    If {
+        cond: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Text(String),
+    Var(String, Vec<Filter>),
+    ForStart(String, String),
+    EndFor,
+    IfStart(String),
+    EndIf,
+}
+
+enum Stop {
+    EndFor,
+    EndIf,
+}
+
+/// Renders `template` against `root`, returning the resulting string.
+pub fn render(template: &str, root: &Context) -> Result<String, Box<dyn Error>> {
+    let tokens = tokenize(template);
+    let mut pos = 0;
+    let nodes = parse_block(&tokens, &mut pos, None)?;
+
+    let mut scopes = vec![root.clone()];
+    Ok(render_nodes(&nodes, &mut scopes))
+}
+
+fn tokenize(template: &str) -> Vec<Token> {
+    let re = Regex::new(
+        r"\{\{\s*([\w.]+)((?:\s*\|\s*\w+(?:\([^()]*\))?)*)\s*\}\}|\{%\s*for\s+(\w+)\s+in\s+([\w.]+)\s*%\}|\{%\s*endfor\s*%\}|\{%\s*if\s+([\w.]+)\s*%\}|\{%\s*endif\s*%\}",
+    )
+    .expect("should never panic");
+
+    let mut tokens = vec![];
+    let mut last_end = 0;
+
+    for cap in re.captures_iter(template) {
+        let whole = cap.get(0).expect("capture 0 always matches");
+        if whole.start() > last_end {
+            tokens.push(Token::Text(template[last_end..whole.start()].to_owned()));
+        }
+
+        if let Some(var) = cap.get(1) {
+            let filters = cap.get(2).map_or("", |m| m.as_str());
+            tokens.push(Token::Var(var.as_str().to_owned(), parse_filters(filters)));
+        } else if let Some(var) = cap.get(3) {
+            let iter = cap
+                .get(4)
+                .expect("for-loop iterable is captured together with its variable")
+                .as_str();
+            tokens.push(Token::ForStart(var.as_str().to_owned(), iter.to_owned()));
+        } else if let Some(cond) = cap.get(5) {
+            tokens.push(Token::IfStart(cond.as_str().to_owned()));
+        } else if whole.as_str().contains("endfor") {
+            tokens.push(Token::EndFor);
+        } else {
+            tokens.push(Token::EndIf);
+        }
+
+        last_end = whole.end();
+    }
+
+    if last_end < template.len() {
+        tokens.push(Token::Text(template[last_end..].to_owned()));
+    }
+
+    tokens
+}
+
+/// Parses tokens into a node tree. `stop` names the closing tag this call is
+/// responsible for consuming (`None` at the top level, where running out of
+/// tokens is the only valid end).
+fn parse_block(
+    tokens: &[Token],
+    pos: &mut usize,
+    stop: Option<Stop>,
+) -> Result<Vec<Node>, Box<dyn Error>> {
+    let mut nodes = vec![];
+
+    while *pos < tokens.len() {
+        match &tokens[*pos] {
+            Token::Text(t) => {
+                nodes.push(Node::Text(t.clone()));
+                *pos += 1;
+            }
+            Token::Var(v, filters) => {
+                nodes.push(Node::Var(v.clone(), filters.clone()));
+                *pos += 1;
+            }
+            Token::ForStart(var, iter) => {
+                let (var, iter) = (var.clone(), iter.clone());
+                *pos += 1;
+                let body = parse_block(tokens, pos, Some(Stop::EndFor))?;
+                nodes.push(Node::For { var, iter, body });
+            }
+            Token::IfStart(cond) => {
+                let cond = cond.clone();
+                *pos += 1;
+                let body = parse_block(tokens, pos, Some(Stop::EndIf))?;
+                nodes.push(Node::If { cond, body });
+            }
+            Token::EndFor => {
+                if matches!(stop, Some(Stop::EndFor)) {
+                    *pos += 1;
+                    return Ok(nodes);
+                }
+                return Err("Unexpected '{% endfor %}' in template".into());
+            }
+            Token::EndIf => {
+                if matches!(stop, Some(Stop::EndIf)) {
+                    *pos += 1;
+                    return Ok(nodes);
+                }
+                return Err("Unexpected '{% endif %}' in template".into());
+            }
+        }
+    }
+
+    match stop {
+        None => Ok(nodes),
+        Some(Stop::EndFor) => Err("Missing '{% endfor %}' in template".into()),
+        Some(Stop::EndIf) => Err("Missing '{% endif %}' in template".into()),
+    }
+}
+
+fn render_nodes(nodes: &[Node], scopes: &mut Vec<Context>) -> String {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Var(path, filters) => {
+                if let Some(v) = resolve(path, scopes) {
+                    let mut s = v.to_display();
+                    for filter in filters {
+                        s = apply_filter(filter, s);
+                    }
+                    out.push_str(&s);
+                }
+            }
+            Node::For { var, iter, body } => {
+                if let Some(Value::List(items)) = resolve(iter, scopes) {
+                    for item in items {
+                        let mut scope = Context::new();
+                        scope.insert(var.clone(), item);
+                        scopes.push(scope);
+                        out.push_str(&render_nodes(body, scopes));
+                        scopes.pop();
+                    }
+                }
+            }
+            Node::If { cond, body } => {
+                if resolve(cond, scopes).is_some_and(|v| v.is_truthy()) {
+                    out.push_str(&render_nodes(body, scopes));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+/// Resolves a dotted variable path (e.g. `commit.title`) against the innermost
+/// matching scope, descending into [`Value::Object`]s for the remaining segments.
+fn resolve(path: &str, scopes: &[Context]) -> Option<Value> {
+    let mut parts = path.split('.');
+    let first = parts.next()?;
+
+    let mut current = scopes.iter().rev().find_map(|s| s.get(first).cloned())?;
+
+    for part in parts {
+        match current {
+            Value::Object(ctx) => current = ctx.get(part)?.clone(),
+            _ => return None,
+        }
+    }
+
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_substitutes_variables() {
+        let mut ctx = Context::new();
+        ctx.insert("name".to_owned(), Value::from("world"));
+
+        assert_eq!(render("Hello, {{ name }}!", &ctx).unwrap(), "Hello, world!");
+    }
+
+    #[test]
+    fn render_loops_over_lists_with_dotted_access() {
+        let mut item1 = Context::new();
+        item1.insert("title".to_owned(), Value::from("first"));
+        let mut item2 = Context::new();
+        item2.insert("title".to_owned(), Value::from("second"));
+
+        let mut ctx = Context::new();
+        ctx.insert(
+            "commits".to_owned(),
+            Value::List(vec![Value::Object(item1), Value::Object(item2)]),
+        );
+
+        let out = render("{% for c in commits %}* {{ c.title }}\n{% endfor %}", &ctx).unwrap();
+        assert_eq!(out, "* first\n* second\n");
+    }
+
+    #[test]
+    fn render_if_skips_false_branch() {
+        let mut ctx = Context::new();
+        ctx.insert("enabled".to_owned(), Value::Bool(false));
+        ctx.insert("description".to_owned(), Value::from(""));
+
+        let out = render(
+            "{% if enabled %}on{% endif %}{% if description %}has description{% endif %}",
+            &ctx,
+        )
+        .unwrap();
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn render_applies_upper_first_filter() {
+        let mut ctx = Context::new();
+        ctx.insert("title".to_owned(), Value::from("fix a bug"));
+
+        assert_eq!(
+            render("{{ title | upper_first }}", &ctx).unwrap(),
+            "Fix a bug"
+        );
+    }
+
+    #[test]
+    fn render_applies_trim_start_matches_filter() {
+        let mut ctx = Context::new();
+        ctx.insert("title".to_owned(), Value::from("fix: a bug"));
+
+        assert_eq!(
+            render(r#"{{ title | trim_start_matches("fix: ") }}"#, &ctx).unwrap(),
+            "a bug"
+        );
+    }
+
+    #[test]
+    fn render_chains_filters() {
+        let mut ctx = Context::new();
+        ctx.insert("title".to_owned(), Value::from("fix: a bug"));
+
+        assert_eq!(
+            render(
+                r#"{{ title | trim_start_matches("fix: ") | upper_first }}"#,
+                &ctx
+            )
+            .unwrap(),
+            "A bug"
+        );
+    }
+
+    #[test]
+    fn render_errors_on_unclosed_block() {
+        let ctx = Context::new();
+        let res = render("{% for c in commits %}{{ c.title }}", &ctx);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Missing '{% endfor %}' in template"
+        );
+    }
+}
@@ -9,7 +9,7 @@ use std::path::PathBuf;
 pub struct Config {
     /// The name of the called command
     pub command: Command,
-    /// Path to the config (template) file
+    /// Path to the config (template) file, YAML or TOML (picked by its extension)
     pub file_path: std::path::PathBuf,
     /// Path to the git repository
     pub git_path: Option<std::path::PathBuf>,
@@ -19,6 +19,38 @@ pub struct Config {
     pub project: Option<String>,
     /// Read commit(s) from stdin
     pub read_from_stdin: bool,
+    /// Commit hashes (or prefixes) to skip, collected from repeatable `--skip-commit` arguments.
+    pub skip_commits: Vec<String>,
+    /// Explicit `<rev>..<rev>` range of commits to process, e.g. for per-release changelogs.
+    pub range: Option<String>,
+    /// Process commits since the most recent tag reachable from `HEAD`.
+    pub latest: bool,
+    /// Only include commits at or after this date.
+    pub since: Option<String>,
+    /// Only include commits at or before this date.
+    pub until: Option<String>,
+    /// Restrict to commits touching this path, e.g. to scope a multi-project monorepo's
+    /// changelog to one project's subtree.
+    pub commit_path: Option<PathBuf>,
+    /// Group the generated changelog into per-release sections, delimited by the
+    /// repository's tags, instead of one flat block.
+    pub group_by_release: bool,
+    /// Restricts the tags considered for `group_by_release` to ones matching this glob
+    /// (e.g. `v*`). `None` considers every tag.
+    pub tag_pattern: Option<String>,
+    /// Parse commits lacking a `changelog:` key as Conventional Commits, same as
+    /// `commit-style: conventional` in the template.
+    pub conventional: bool,
+    /// Path to write the generated changelog to, overwriting any previous content.
+    pub output: Option<PathBuf>,
+    /// Path to a changelog file to prepend the generated changelog to, preserving its
+    /// existing content below. Creates the file if it doesn't exist yet.
+    pub prepend: Option<PathBuf>,
+    /// Output representation for the generated changelog.
+    pub format: OutputFormat,
+    /// Path to a changelog already generated with `--format json`, to re-render from
+    /// instead of walking git history again.
+    pub from_json: Option<PathBuf>,
 }
 
 impl Config {
@@ -33,10 +65,42 @@ impl Config {
             commit_id: args.commit,
             project: args.project,
             read_from_stdin: args.from_stdin,
+            skip_commits: args.skip_commit,
+            range: args.range,
+            latest: args.latest,
+            since: args.since,
+            until: args.until,
+            commit_path: args.commit_path,
+            group_by_release: args.group_by_release,
+            tag_pattern: args.tag_pattern,
+            conventional: args.conventional,
+            output: args.output,
+            prepend: args.prepend,
+            format: args.format,
+            from_json: args.from_json,
         })
     }
 }
 
+/// Name of the ignore file (one commit hash or prefix per line, `#` comments allowed)
+/// looked up in the git repository's directory, borrowing git-cliff's `.cliffignore` idea.
+const IGNORE_FILENAME: &str = ".mkchlogignore";
+
+/// Reads commit hashes/prefixes to skip from `<git_path>/.mkchlogignore`. A missing file
+/// is treated as empty, since most repositories won't need one.
+pub fn read_skip_commits_file(git_path: &std::path::Path) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(git_path.join(IGNORE_FILENAME)) else {
+        return vec![];
+    };
+
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
 /// Application arguments
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -49,7 +113,8 @@ struct Args {
     #[arg(short, long)]
     commit: Option<String>,
 
-    /// Optional path to the YAML template file [default: ".mkchlog.yml"]
+    /// Optional path to the template file, YAML or TOML (picked by extension,
+    /// e.g. ".mkchlog.toml") [default: ".mkchlog.yml"]
     #[arg(short, long)]
     file_path: Option<PathBuf>,
 
@@ -61,10 +126,83 @@ struct Args {
     #[arg(long, default_value_t = false)]
     from_stdin: bool,
 
+    /// Commit hash (or prefix) to skip; can be repeated. See also the `.mkchlogignore` file.
+    #[arg(long = "skip-commit")]
+    skip_commit: Vec<String>,
+
+    /// Explicit revision range to process, e.g. "v1.0.0..HEAD". Overrides `--commit`/`--latest`.
+    #[arg(long, conflicts_with = "latest")]
+    range: Option<String>,
+
+    /// Process commits since the most recent tag reachable from HEAD. Overrides `--commit`.
+    #[arg(long, default_value_t = false, conflicts_with = "range")]
+    latest: bool,
+
+    /// Only include commits at or after this date (e.g. "2024-01-01" or "2 weeks ago"),
+    /// as accepted by `git log --since`. Composes with `--range`/`--latest`/`--commit`.
+    #[arg(long)]
+    since: Option<String>,
+
+    /// Only include commits at or before this date, as accepted by `git log --until`.
+    /// Composes with `--range`/`--latest`/`--commit`.
+    #[arg(long)]
+    until: Option<String>,
+
+    /// Restrict to commits touching this path, e.g. to scope a multi-project monorepo's
+    /// changelog to one project's subtree. Passed to `git log` as `-- <path>`.
+    #[arg(long = "commit-path")]
+    commit_path: Option<PathBuf>,
+
+    /// Group the generated changelog into per-release sections delimited by the
+    /// repository's tags, with an "Unreleased" section for commits newer than the latest one.
+    #[arg(long, default_value_t = false)]
+    group_by_release: bool,
+
+    /// Restrict `--group-by-release` to tags matching this glob (e.g. "v*"), as accepted
+    /// by `git tag --list`. Has no effect unless `--group-by-release` is also set.
+    #[arg(long)]
+    tag_pattern: Option<String>,
+
+    /// Parse commits lacking a `changelog:` key as Conventional Commits, same as setting
+    /// `commit-style: conventional` in the template.
+    #[arg(long, default_value_t = false)]
+    conventional: bool,
+
+    /// Write the generated changelog to this path instead of stdout, overwriting any previous content.
+    #[arg(short, long, conflicts_with = "prepend")]
+    output: Option<PathBuf>,
+
+    /// Prepend the generated changelog to this file, above its existing content, instead of
+    /// printing to stdout. Implies `--latest` unless `--range`/`--commit` is also given, so
+    /// by default only the newest release's commits are added rather than the whole history.
+    #[arg(long, conflicts_with = "output")]
+    prepend: Option<PathBuf>,
+
+    /// Output representation for the generated changelog.
+    #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+    format: OutputFormat,
+
+    /// Re-render from a changelog previously generated with `--format json`, instead of
+    /// walking git history again. Useful for caching the (potentially expensive) git walk
+    /// in CI and regenerating human-readable output from the cached artifact later.
+    #[arg(long, conflicts_with_all = ["from_stdin", "commit", "range", "latest", "since", "until", "commit_path", "skip_commit"])]
+    from_json: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Command,
 }
 
+/// Output representation for a generated changelog.
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Debug)]
+pub enum OutputFormat {
+    /// The hand-formatted (or `body-template`-rendered) Markdown layout.
+    Markdown,
+    /// Structured JSON: releases, each with sections/subsections of commit objects
+    /// (`title`, `description`, commit hash, author, date, `title_is_enough`), so the
+    /// same parsed data the Markdown renderer consumes can be piped into other tooling.
+    Json,
+}
+
 /// Application commands
 #[derive(Subcommand, PartialEq)]
 pub enum Command {
@@ -7,27 +7,78 @@ pub mod stdin;
 use self::commit::Commit;
 use std::error::Error;
 
+/// Output of [`GitLogCommand::get_log`]: the raw `git log` text used to build [`Commit`]s,
+/// plus (when tag-aware release grouping was requested) the repository's tags paired with
+/// the commit each points at, newest tag first. Bundled into one struct, rather than the
+/// bare `String` of old, so a single call can hand [`Git::releases`] everything it needs.
+pub struct GitLogOutput {
+    /// Raw output of `git log`, in the format [`Commit::new`] expects.
+    pub log: String,
+    /// `(tag name, commit id, commit date)` triples, newest tag first. Empty unless the
+    /// [`GitLogCommand`] was asked to resolve tags, e.g. via `GitLogCmd::group_by_release`.
+    pub tags: Vec<(String, String, String)>,
+}
+
 /// Trait that represents the `git log` command functionality
 pub trait GitLogCommand {
     /// Returns the output of the `git log` command
-    fn get_log(&self) -> Result<String, Box<dyn Error>>;
+    fn get_log(&self) -> Result<GitLogOutput, Box<dyn Error>>;
+}
+
+/// One release's worth of commits, as grouped by [`Git::releases`].
+#[derive(Debug)]
+pub struct Release {
+    /// Tag name, or `None` for the "Unreleased" bucket of commits newer than the latest tag.
+    pub version: Option<String>,
+    /// Date the tag points at, or `None` for the "Unreleased" bucket.
+    pub date: Option<String>,
+    pub commits: Vec<Commit>,
 }
 
 /// Git object for interaction with `git` command
 pub struct Git {
     git_log_cmd: Box<dyn GitLogCommand>,
+    /// Commit hashes (or prefixes) to drop from [`Git::commits`], e.g. from `--skip-commit`
+    /// or a `.mkchlogignore` file.
+    skip_commits: Vec<String>,
 }
 
 impl Git {
     /// Creates a new [`Git`] object that uses `git_log_cmd` to obtain the commits.
     pub fn new(git_log_cmd: Box<dyn GitLogCommand>) -> Self {
-        Self { git_log_cmd }
+        Self {
+            git_log_cmd,
+            skip_commits: vec![],
+        }
     }
 
-    /// Parses the output of the [`GitLogCommand`] and returns the collection of commits.
+    /// Sets commit hashes (or prefixes) that [`Git::commits`]/[`Git::releases`] should drop.
+    pub fn skip_commits(mut self, skip_commits: Vec<String>) -> Self {
+        self.skip_commits = skip_commits;
+        self
+    }
+
+    /// Parses the output of the [`GitLogCommand`] and returns the collection of commits,
+    /// excluding any whose hash matches a [`Git::skip_commits`] entry, ignoring any release
+    /// grouping. Equivalent to flattening [`Git::releases`] back into one list.
     pub fn commits(&self) -> Result<Vec<Commit>, Box<dyn Error>> {
-        let git_log = self.git_log_cmd.get_log()?;
+        let output = self.git_log_cmd.get_log()?;
+        self.parse_commits(&output.log)
+    }
+
+    /// Like [`Git::commits`], but additionally groups the commits into [`Release`] buckets
+    /// using the tags resolved by the [`GitLogCommand`]. When no tags were resolved, this
+    /// returns (at most) a single bucket with `version: None` containing every commit.
+    pub fn releases(&self) -> Result<Vec<Release>, Box<dyn Error>> {
+        let output = self.git_log_cmd.get_log()?;
+        let commits = self.parse_commits(&output.log)?;
+
+        Ok(group_into_releases(commits, &output.tags))
+    }
 
+    /// Splits `git_log` (the raw `git log` output) into individual [`Commit`]s, excluding
+    /// any whose hash matches a [`Git::skip_commits`] entry.
+    fn parse_commits(&self, git_log: &str) -> Result<Vec<Commit>, Box<dyn Error>> {
         // NB: `Regex::new(r"(?m)^commit [a-z|\d]{40}$")` was previously used to split the commits
         // however for some unknown reason it would cause `npm` to silently exit with success code when ran in WASM.
         // This workarounds the issue.
@@ -44,7 +95,13 @@ impl Git {
                 None => git_log.len(),
             };
             let commit = Commit::new(&git_log[pos..copy_up_to])?;
-            commits.push(commit);
+            if !self
+                .skip_commits
+                .iter()
+                .any(|skip| commit.commit_id.starts_with(skip.as_str()))
+            {
+                commits.push(commit);
+            }
             if end.is_none() {
                 break;
             } else {
@@ -56,14 +113,64 @@ impl Git {
     }
 }
 
+/// Buckets `commits` (newest first, as `git log` lists them) into [`Release`]s using `tags`
+/// (newest tag first, each paired with the commit it points at and that commit's date):
+/// every commit from a tag's commit down to (but not including) the next older tag's commit
+/// belongs to that tag's release, and anything newer than the first tag lands in the `None`
+/// ("Unreleased") bucket. Releases left with no commits are dropped.
+fn group_into_releases(commits: Vec<Commit>, tags: &[(String, String, String)]) -> Vec<Release> {
+    let mut releases = Vec::new();
+    let mut current_version = None;
+    let mut current_date = None;
+    let mut current_commits = Vec::new();
+    let mut tags = tags.iter().peekable();
+
+    for commit in commits {
+        if let Some((tag_name, tag_commit, tag_date)) = tags.peek() {
+            if *tag_commit == commit.commit_id {
+                releases.push(Release {
+                    version: current_version.take(),
+                    date: current_date.take(),
+                    commits: std::mem::take(&mut current_commits),
+                });
+                current_version = Some(tag_name.clone());
+                current_date = Some(tag_date.clone());
+                tags.next();
+            }
+        }
+        current_commits.push(commit);
+    }
+
+    releases.push(Release {
+        version: current_version,
+        date: current_date,
+        commits: current_commits,
+    });
+
+    releases.retain(|release| !release.commits.is_empty());
+    releases
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    pub struct GitCmdMock;
+    pub struct GitCmdMock {
+        tags: Vec<(String, String, String)>,
+    }
+
+    impl GitCmdMock {
+        fn new() -> Self {
+            Self { tags: vec![] }
+        }
+
+        fn tags(tags: Vec<(String, String, String)>) -> Self {
+            Self { tags }
+        }
+    }
 
     impl GitLogCommand for GitCmdMock {
-        fn get_log(&self) -> Result<String, Box<dyn Error>> {
+        fn get_log(&self) -> Result<GitLogOutput, Box<dyn Error>> {
             let ouput = "\
 commit a1a654e256cc96e1c4b5a81845b5e3f3f0aa9ed3
 Author: Cry Wolf <cry.wolf@centrum.cz>
@@ -88,15 +195,64 @@ Date:   Tue Jun 13 16:24:22 2023 +0200
     changelog:
         section: features";
 
-            Ok(ouput.to_string())
+            Ok(GitLogOutput {
+                log: ouput.to_string(),
+                tags: self.tags.clone(),
+            })
         }
     }
 
     #[test]
     fn git_commits() {
-        let git = Git::new(Box::new(GitCmdMock));
+        let git = Git::new(Box::new(GitCmdMock::new()));
+
+        let res = git.commits().unwrap();
+        assert_eq!(res.len(), 2);
+    }
+
+    #[test]
+    fn git_commits_drops_skipped_commits_by_prefix() {
+        let git = Git::new(Box::new(GitCmdMock::new())).skip_commits(vec!["62db026".to_owned()]);
 
         let res = git.commits().unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].commit_id, "a1a654e256cc96e1c4b5a81845b5e3f3f0aa9ed3");
+    }
+
+    #[test]
+    fn git_releases_without_tags_is_one_unreleased_bucket() {
+        let git = Git::new(Box::new(GitCmdMock::new()));
+
+        let res = git.releases().unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].version, None);
+        assert_eq!(res[0].commits.len(), 2);
+    }
+
+    #[test]
+    fn git_releases_groups_commits_by_tag_boundary() {
+        let git = Git::new(Box::new(GitCmdMock::tags(vec![(
+            "v1.0.0".to_owned(),
+            "62db026b0ead7f0659df10c70e402c70ede5d7dd".to_owned(),
+            "2023-06-13".to_owned(),
+        )])));
+
+        let res = git.releases().unwrap();
+
         assert_eq!(res.len(), 2);
+        assert_eq!(res[0].version, None);
+        assert_eq!(res[0].date, None);
+        assert_eq!(res[0].commits.len(), 1);
+        assert_eq!(
+            res[0].commits[0].commit_id,
+            "a1a654e256cc96e1c4b5a81845b5e3f3f0aa9ed3"
+        );
+        assert_eq!(res[1].version.as_deref(), Some("v1.0.0"));
+        assert_eq!(res[1].date.as_deref(), Some("2023-06-13"));
+        assert_eq!(res[1].commits.len(), 1);
+        assert_eq!(
+            res[1].commits[0].commit_id,
+            "62db026b0ead7f0659df10c70e402c70ede5d7dd"
+        );
     }
 }
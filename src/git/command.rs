@@ -1,5 +1,7 @@
 //! `git log` command implementation
 
+use regex::Regex;
+use std::collections::HashMap;
 use std::error::Error;
 use std::path::PathBuf;
 
@@ -7,26 +9,279 @@ use std::path::PathBuf;
 pub struct GitLogCmd {
     path: PathBuf,
     commit_id: Option<String>,
+    /// Explicit `<rev>..<rev>` range, taking precedence over `commit_id`/`latest`.
+    range: Option<String>,
+    /// Process commits since the most recent tag reachable from `HEAD`, taking precedence over `commit_id`.
+    latest: bool,
+    /// Resolve tags so [`super::GitLogOutput::tags`] lets [`super::Git::releases`] group
+    /// commits by release.
+    group_by_release: bool,
+    /// Restricts [`GitLogCmd::tags`] to tags matching this glob (e.g. `v*`), passed to
+    /// `git tag --list` as-is. `None` resolves every tag.
+    tag_pattern: Option<String>,
+    /// Only include commits at or after this date, passed to `git log` as `--since`.
+    since: Option<String>,
+    /// Only include commits at or before this date, passed to `git log` as `--until`.
+    until: Option<String>,
+    /// Restrict to commits touching this path, passed to `git log` as `-- <path>`.
+    path_filter: Option<PathBuf>,
 }
 
 impl GitLogCmd {
     /// Creates a new [`GitLogCmd`]. Accepts the path to the `git` repository and optional commit number.
     pub fn new(path: PathBuf, commit_id: Option<String>) -> Self {
-        Self { path, commit_id }
+        Self {
+            path,
+            commit_id,
+            range: None,
+            latest: false,
+            group_by_release: false,
+            tag_pattern: None,
+            since: None,
+            until: None,
+            path_filter: None,
+        }
+    }
+
+    /// Sets an explicit `<rev>..<rev>` range to process, overriding `commit_id`/`latest`.
+    pub fn range(mut self, range: Option<String>) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// When `true`, processes commits since the most recent tag reachable from `HEAD`,
+    /// overriding `commit_id`.
+    pub fn latest(mut self, latest: bool) -> Self {
+        self.latest = latest;
+        self
+    }
+
+    /// When `true`, additionally resolves the repository's tags (see [`GitLogCmd::tags`])
+    /// so [`super::Git::releases`] can group commits into per-version buckets.
+    pub fn group_by_release(mut self, group_by_release: bool) -> Self {
+        self.group_by_release = group_by_release;
+        self
+    }
+
+    /// Restricts [`GitLogCmd::tags`] to tags matching `tag_pattern` (a glob, e.g. `v*`, as
+    /// accepted by `git tag --list`). Has no effect unless `group_by_release` is also set.
+    pub fn tag_pattern(mut self, tag_pattern: Option<String>) -> Self {
+        self.tag_pattern = tag_pattern;
+        self
+    }
+
+    /// Only includes commits at or after `since` (any date `git log --since` accepts, e.g.
+    /// `2024-01-01` or `"2 weeks ago"`). Composes with `range`/`latest`/`commit_id`.
+    pub fn since(mut self, since: Option<String>) -> Self {
+        self.since = since;
+        self
+    }
+
+    /// Only includes commits at or before `until`. Composes with `range`/`latest`/`commit_id`.
+    pub fn until(mut self, until: Option<String>) -> Self {
+        self.until = until;
+        self
+    }
+
+    /// Restricts to commits touching `path`, so a multi-project monorepo can scope a
+    /// project's changelog to its own subtree. Composes with `range`/`latest`/`commit_id`.
+    pub fn path(mut self, path: Option<PathBuf>) -> Self {
+        self.path_filter = path;
+        self
+    }
+
+    /// Checks the configured options for obvious mistakes before shelling out to `git`, so
+    /// a bad range/date/path produces an actionable error instead of `git log`'s own.
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(range) = &self.range {
+            if !range.contains("..") {
+                return Err(format!(
+                    "Revision range '{}' must be of the form '<rev>..<rev>'",
+                    range
+                )
+                .into());
+            }
+        }
+
+        if let Some(since) = &self.since {
+            if since.trim().is_empty() {
+                return Err("'--since' value must not be empty".into());
+            }
+        }
+
+        if let Some(until) = &self.until {
+            if until.trim().is_empty() {
+                return Err("'--until' value must not be empty".into());
+            }
+        }
+
+        if let Some(path) = &self.path_filter {
+            if path.as_os_str().is_empty() {
+                return Err("commit path filter must not be empty".into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the repository's tags reachable from `HEAD`, newest first (via `git tag
+    /// --merged --sort=-creatordate`, restricted to `tag_pattern` if set), paired with the
+    /// commit each points at and that commit's date (resolved via `git log
+    /// --format=%H|%ad|%D --date=short`, which lists every commit's decorations).
+    fn tags(&self) -> Result<Vec<(String, String, String)>, Box<dyn Error>> {
+        let mut tag_list_cmd = std::process::Command::new("git");
+        tag_list_cmd
+            .arg("-C")
+            .arg(&self.path)
+            .arg("tag")
+            .arg("--merged")
+            .arg("--sort=-creatordate");
+
+        if let Some(tag_pattern) = &self.tag_pattern {
+            tag_list_cmd.arg("--list").arg(tag_pattern);
+        }
+
+        let tag_list_output = tag_list_cmd.output().map_err(|err| {
+            format!(
+                "Failed to execute '{}' command: {}",
+                tag_list_cmd.get_program().to_str().unwrap_or("git"),
+                err
+            )
+        })?;
+
+        if !tag_list_output.status.success() {
+            return Err(format!(
+                "Failed to execute 'git tag --merged --sort=-creatordate' command:\n{}",
+                String::from_utf8_lossy(&tag_list_output.stderr)
+            )
+            .into());
+        }
+
+        let tag_names: Vec<String> = String::from_utf8_lossy(&tag_list_output.stdout)
+            .lines()
+            .map(str::to_owned)
+            .collect();
+
+        let mut decorate_cmd = std::process::Command::new("git");
+        decorate_cmd
+            .arg("-C")
+            .arg(&self.path)
+            .arg("log")
+            .arg("--format=%H|%ad|%D")
+            .arg("--date=short");
+
+        let decorate_output = decorate_cmd.output().map_err(|err| {
+            format!(
+                "Failed to execute '{}' command: {}",
+                decorate_cmd.get_program().to_str().unwrap_or("git"),
+                err
+            )
+        })?;
+
+        if !decorate_output.status.success() {
+            return Err(format!(
+                "Failed to execute 'git log --format=%H|%ad|%D --date=short' command:\n{}",
+                String::from_utf8_lossy(&decorate_output.stderr)
+            )
+            .into());
+        }
+
+        let tag_ref_re = Regex::new(r"tag:\s*([^,\s]+)").expect("should never panic");
+        let mut commit_by_tag: HashMap<String, (String, String)> = HashMap::new();
+        for line in String::from_utf8_lossy(&decorate_output.stdout).lines() {
+            let mut parts = line.splitn(3, '|');
+            let (Some(commit_id), Some(date), Some(refs)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+
+            for caps in tag_ref_re.captures_iter(refs) {
+                commit_by_tag
+                    .entry(caps[1].to_owned())
+                    .or_insert_with(|| (commit_id.to_owned(), date.to_owned()));
+            }
+        }
+
+        Ok(tag_names
+            .into_iter()
+            .filter_map(|tag| {
+                commit_by_tag
+                    .get(&tag)
+                    .map(|(commit_id, date)| (tag.clone(), commit_id.clone(), date.clone()))
+            })
+            .collect())
+    }
+
+    /// Returns the most recent tag reachable from `HEAD`, via `git describe --tags --abbrev=0`.
+    fn latest_tag(&self) -> Result<String, Box<dyn Error>> {
+        let mut git_command = std::process::Command::new("git");
+        git_command
+            .arg("-C")
+            .arg(&self.path)
+            .arg("describe")
+            .arg("--tags")
+            .arg("--abbrev=0");
+
+        let output = git_command.output().map_err(|err| {
+            format!(
+                "Failed to execute '{}' command: {}",
+                git_command.get_program().to_str().unwrap_or("git"),
+                err
+            )
+        })?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to execute 'git describe --tags --abbrev=0' command:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    }
+
+    /// Returns the revision argument passed to `git log`, if any: an explicit `range`
+    /// takes precedence, then `latest` (resolved to `<tag>..HEAD`), then `commit_id`
+    /// (kept as the `skip-commits-up-to` lower bound, resolved to `<commit_id>..HEAD`).
+    fn revision(&self) -> Result<Option<String>, Box<dyn Error>> {
+        if let Some(range) = &self.range {
+            return Ok(Some(range.clone()));
+        }
+
+        if self.latest {
+            return Ok(Some(format!("{}..HEAD", self.latest_tag()?)));
+        }
+
+        Ok(self.commit_id.as_ref().map(|commit_id| format!("{}..HEAD", commit_id)))
     }
 }
 
 impl super::GitLogCommand for GitLogCmd {
-    fn get_log(&self) -> Result<String, Box<dyn Error>> {
+    fn get_log(&self) -> Result<super::GitLogOutput, Box<dyn Error>> {
+        self.validate()?;
+
         let mut git_command = std::process::Command::new("git");
-        git_command.arg("-C").arg(&self.path).arg("log");
+        git_command
+            .arg("-C")
+            .arg(&self.path)
+            .arg("log")
+            .arg("--no-merges");
+
+        if let Some(revision) = self.revision()? {
+            git_command.arg(revision);
+        }
+
+        if let Some(since) = &self.since {
+            git_command.arg(format!("--since={}", since));
+        }
+
+        if let Some(until) = &self.until {
+            git_command.arg(format!("--until={}", until));
+        }
 
-        if self.commit_id.is_some() {
-            // add argument: git log 7c85bee4303d56bededdfacf8fbb7bdc68e2195b..HEAD
-            git_command.arg(format!(
-                "{}..HEAD",
-                self.commit_id.as_ref().expect("commit_id is not empty")
-            ));
+        if let Some(path) = &self.path_filter {
+            git_command.arg("--").arg(path);
         }
 
         let git_cmd_output = git_command.output().map_err(|err| {
@@ -51,8 +306,47 @@ impl super::GitLogCommand for GitLogCmd {
             .into());
         }
 
-        let git_log = String::from_utf8_lossy(&git_cmd_output.stdout);
+        let log = String::from_utf8_lossy(&git_cmd_output.stdout).into_owned();
+
+        let tags = if self.group_by_release {
+            self.tags()?
+        } else {
+            vec![]
+        };
+
+        Ok(super::GitLogOutput { log, tags })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_rejects_range_without_dotdot() {
+        let cmd = GitLogCmd::new(PathBuf::from("."), None).range(Some("v1.0.0".to_owned()));
+        assert!(cmd.validate().is_err());
+    }
+
+    #[test]
+    fn validate_accepts_well_formed_range() {
+        let cmd =
+            GitLogCmd::new(PathBuf::from("."), None).range(Some("v1.0.0..v2.0.0".to_owned()));
+        assert!(cmd.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_blank_since_and_until() {
+        let since = GitLogCmd::new(PathBuf::from("."), None).since(Some("  ".to_owned()));
+        assert!(since.validate().is_err());
+
+        let until = GitLogCmd::new(PathBuf::from("."), None).until(Some("".to_owned()));
+        assert!(until.validate().is_err());
+    }
 
-        Ok(git_log.into_owned())
+    #[test]
+    fn validate_rejects_empty_path_filter() {
+        let cmd = GitLogCmd::new(PathBuf::from("."), None).path(Some(PathBuf::from("")));
+        assert!(cmd.validate().is_err());
     }
 }
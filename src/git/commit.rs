@@ -12,10 +12,33 @@ pub struct Commit {
     pub header: String,
     /// Git commit message
     pub message: String,
-    /// Changelog message extracted from the commit message
+    /// Changelog message extracted from the commit message, i.e. everything after a
+    /// `changelog:` key. Empty when the commit has no such key, e.g. a Conventional Commit
+    /// processed with `commit-style: conventional`; it is up to the caller to decide whether
+    /// that's an error.
     pub changelog_message: String,
     /// Raw data of the commit
     pub raw_data: String,
+    /// Author name, parsed from the header's `Author:` line (empty when there is none)
+    pub author_name: String,
+    /// Author email, parsed from the header's `Author:` line (empty when there is none)
+    pub author_email: String,
+    /// Commit date, parsed from the header's `Date:` line (kept as git prints it; empty
+    /// when there is none)
+    pub date: String,
+}
+
+impl Commit {
+    /// Returns the first 7 characters of [`Commit::commit_id`], matching how `git log --oneline`
+    /// abbreviates hashes.
+    pub fn commit_id_short(&self) -> &str {
+        let end = self
+            .commit_id
+            .char_indices()
+            .nth(7)
+            .map_or(self.commit_id.len(), |(i, _)| i);
+        &self.commit_id[..end]
+    }
 }
 
 impl Commit {
@@ -39,10 +62,9 @@ impl Commit {
                 raw_data
             ))?;
 
-        let changelog = commit_iter.next().map(str::trim).ok_or(format!(
-            "Missing 'changelog:' key in commit:\n>>> {}",
-            raw_data
-        ))?;
+        // no 'changelog:' key is not an error here: whether that's allowed depends on the
+        // configured commit style, which is decided further up in `CommitChangelog::parse`
+        let changelog = commit_iter.next().map(str::trim).unwrap_or_default();
 
         let commit_id = header
             .lines()
@@ -57,12 +79,38 @@ impl Commit {
                 header
             ))?;
 
+        // a header without `Author:`/`Date:` lines (e.g. the one faked for stdin input)
+        // leaves the author and date empty
+        let (author_name, author_email) = match header
+            .lines()
+            .find_map(|l| l.strip_prefix("Author:"))
+        {
+            Some(author_line) => {
+                let author_regex = Regex::new(r"^(.*?)\s*<(.*)>$").expect("should never panic");
+                let author_caps = author_regex.captures(author_line.trim()).ok_or(format!(
+                    "Could not extract author name and email from header:\n>>> {}",
+                    header
+                ))?;
+                (author_caps[1].to_owned(), author_caps[2].to_owned())
+            }
+            None => (String::new(), String::new()),
+        };
+
+        let date = header
+            .lines()
+            .find_map(|l| l.strip_prefix("Date:"))
+            .map(str::trim)
+            .unwrap_or_default();
+
         let commit = Commit {
             commit_id: commit_id.to_owned(),
             header: header.to_owned(),
             message: commit_message.trim().to_owned(),
             changelog_message: changelog.to_owned(),
             raw_data: raw_data.to_owned(),
+            author_name,
+            author_email,
+            date: date.to_owned(),
         };
 
         Ok(commit)
@@ -106,6 +154,10 @@ Date:   Tue Jun 13 16:26:35 2023 +0200";
         assert_eq!(res.header, exp_header);
         assert_eq!(res.message, exp_message);
         assert_eq!(res.changelog_message, exp_changelog_message);
+        assert_eq!(res.commit_id_short(), "7c85bee");
+        assert_eq!(res.author_name, "Cry Wolf");
+        assert_eq!(res.author_email, "cry.wolf@centrum.cz");
+        assert_eq!(res.date, "Tue Jun 13 16:26:35 2023 +0200");
     }
 
     #[test]
@@ -130,26 +182,48 @@ Date:   Tue Jun 13 16:26:35 2023 +0200";
     }
 
     #[test]
-    fn commit_new_missing_changelog_message() {
-        let raw_data = "\
-commit 7c85bee4303d56bededdfacf8fbb7bdc68e2195b
+    fn commit_new_merge_commit_with_gpg_signature() {
+        // merge commit with a `Merge:` line and an (unverified) `gpgsig` block between the
+        // `commit` line and `Author:`/`Date:`, as seen e.g. in GitHub's merge commits
+        let raw_data = "commit 7c85bee4303d56bededdfacf8fbb7bdc68e2195b
+Merge: 1111111 2222222
+gpgsig -----BEGIN PGP SIGNATURE-----
+ 
+ iQIzBAABCAAdFiEE1234567890abcdef1234567890abcdef
+ =abcd
+ -----END PGP SIGNATURE-----
 Author: Cry Wolf <cry.wolf@centrum.cz>
 Date:   Tue Jun 13 16:26:35 2023 +0200
 
-    Don't reallocate the buffer when we know its size
-";
-        let res = Commit::new(raw_data);
-        assert!(res.is_err());
+    Merge branch 'feature'
+
+    changelog:
+        section: perf
+        title: Merged the feature branch
+        title-is-enough: true";
+
+        let res = Commit::new(raw_data).unwrap();
+        assert_eq!(res.commit_id, "7c85bee4303d56bededdfacf8fbb7bdc68e2195b");
+        assert_eq!(res.message, "Merge branch 'feature'");
+        assert_eq!(res.author_name, "Cry Wolf");
+        assert_eq!(res.author_email, "cry.wolf@centrum.cz");
+        assert_eq!(res.date, "Tue Jun 13 16:26:35 2023 +0200");
+    }
 
-        let exp_err = "\
-Missing 'changelog:' key in commit:
->>> commit 7c85bee4303d56bededdfacf8fbb7bdc68e2195b
+    #[test]
+    fn commit_new_missing_changelog_message() {
+        // no 'changelog:' key is no longer an error at this layer: a commit written as a
+        // Conventional Commit has none, and whether that's acceptable is decided by
+        // `CommitChangelog::parse` based on the configured commit style
+        let raw_data = "\
+commit 7c85bee4303d56bededdfacf8fbb7bdc68e2195b
 Author: Cry Wolf <cry.wolf@centrum.cz>
 Date:   Tue Jun 13 16:26:35 2023 +0200
 
     Don't reallocate the buffer when we know its size
 ";
-
-        assert_eq!(res.unwrap_err().to_string(), exp_err);
+        let res = Commit::new(raw_data).unwrap();
+        assert_eq!(res.message, "Don't reallocate the buffer when we know its size");
+        assert_eq!(res.changelog_message, "");
     }
 }
@@ -19,7 +19,7 @@ impl Default for Stdin {
 }
 
 impl super::GitLogCommand for Stdin {
-    fn get_log(&self) -> Result<String, Box<dyn std::error::Error>> {
+    fn get_log(&self) -> Result<super::GitLogOutput, Box<dyn std::error::Error>> {
         let mut stdin = stdin().lock();
         let mut buf = String::new();
         stdin.read_to_string(&mut buf)?;
@@ -30,6 +30,10 @@ impl super::GitLogCommand for Stdin {
             buf.insert_str(0, "commit FROM STDIN\n\n")
         }
 
-        Ok(buf)
+        // reading commit(s) from stdin doesn't support tag-aware release grouping
+        Ok(super::GitLogOutput {
+            log: buf,
+            tags: vec![],
+        })
     }
 }
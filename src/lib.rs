@@ -11,24 +11,42 @@ use crate::changelog::Changelog;
 use crate::config::Command;
 use crate::git::command::GitLogCmd;
 use crate::git::Git;
+use crate::template::CommitStyle;
 use crate::template::Template;
-use std::fs::File;
 
 /// Entrypoint of the application
 pub fn run(config: config::Config) -> Result<(), Box<dyn std::error::Error>> {
-    let f = match File::open(&config.file_path) {
-        Ok(f) => f,
-        Err(err) => {
-            return Err(format!(
-                "Error reading config YAML file '{}': {}",
-                config.file_path.display(),
-                err
-            )
-            .into())
-        }
-    };
+    // parsed as TOML when `file_path`'s extension is `toml`, otherwise as YAML
+    let mut template = Template::<changelog::Changes>::from_path(&config.file_path)?;
+
+    // CLI flag is sugar for `commit-style: conventional`, overriding the template setting
+    if config.conventional {
+        template.settings.commit_style = Some(CommitStyle::Conventional);
+    }
+
+    // re-render a previously cached `--format json` changelog instead of walking git
+    // history again; the template is still needed for the output templates below
+    if let Some(path) = &config.from_json {
+        let json = std::fs::read_to_string(path).map_err(|err| {
+            format!("Error reading cached changelog JSON '{}': {}", path.display(), err)
+        })?;
 
-    let mut template = Template::<changelog::Changes>::new(f)?;
+        let output = changelog::from_json(
+            &json,
+            template.settings.header_template.as_deref(),
+            template.settings.body_template.as_deref(),
+            template.settings.footer_template.as_deref(),
+            template.settings.commit_url_template().as_deref(),
+            config.group_by_release || template.settings.group_by_release(),
+        )?;
+
+        return write_output(
+            &output,
+            config.output,
+            config.prepend,
+            template.settings.header_template.as_deref(),
+        );
+    }
 
     // set value from program arguments or yaml file
     let commit_id = match (
@@ -71,18 +89,83 @@ pub fn run(config: config::Config) -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    let mut skip_commits = config::read_skip_commits_file(&git_path);
+    skip_commits.extend(config.skip_commits);
+
+    // CLI flag overrides the template setting, same precedence as `commit_id`/`git_path` above
+    let group_by_release = config.group_by_release || template.settings.group_by_release();
+    let tag_pattern = config
+        .tag_pattern
+        .or_else(|| template.settings.tag_pattern.clone());
+
+    // CLI arg overrides the template setting, same precedence as `commit_id`/`git_path` above
+    let range = config.range.or_else(|| template.settings.range.clone());
+
+    // `--prepend` without an explicit `--range`/`--commit` is almost always meant as "only
+    // this release's worth of commits", same as running with `--latest` by hand, so splicing
+    // onto an existing CHANGELOG.md doesn't require repeating both flags every time
+    let latest = config.latest
+        || template.settings.latest()
+        || (config.prepend.is_some() && range.is_none() && commit_id.is_none());
+
+    let since = config.since.or_else(|| template.settings.since.clone());
+    let until = config.until.or_else(|| template.settings.until.clone());
+    let commit_path = config
+        .commit_path
+        .or_else(|| template.settings.commit_path.clone());
+
     let git = if config.read_from_stdin {
         use git::stdin::Stdin;
         let git_cmd = Box::new(Stdin::new());
         Git::new(git_cmd)
     } else {
-        let git_cmd = Box::new(GitLogCmd::new(git_path, commit_id));
+        let git_cmd = Box::new(
+            GitLogCmd::new(git_path, commit_id)
+                .range(range)
+                .latest(latest)
+                .since(since)
+                .until(until)
+                .path(commit_path)
+                .group_by_release(group_by_release)
+                .tag_pattern(tag_pattern),
+        );
         Git::new(git_cmd)
-    };
+    }
+    .skip_commits(skip_commits);
 
+    let is_generate = config.command == Command::Generate;
+    let header_template = template.settings.header_template.clone();
     let mut changelog = Changelog::new(&mut template, git);
-    let output = changelog.generate(config.project, config.command)?;
-    println!("{}", output);
+    let output = changelog.generate(config.project, config.command, config.format)?;
+
+    if !is_generate {
+        println!("{}", output);
+        return Ok(());
+    }
+
+    write_output(&output, config.output, config.prepend, header_template.as_deref())
+}
+
+/// Writes `output` to `prepend` (splicing above its existing content), or to `output`
+/// (overwriting it), or to stdout if neither path was given. `header_template` is used to
+/// strip a leading copy of the rendered header from the existing file before prepending.
+fn write_output(
+    output: &str,
+    output_path: Option<std::path::PathBuf>,
+    prepend_path: Option<std::path::PathBuf>,
+    header_template: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(path) = prepend_path {
+        let existing = std::fs::read_to_string(&path).unwrap_or_default();
+        let spliced = changelog::prepend(header_template, output, &existing)?;
+        std::fs::write(&path, spliced)
+            .map_err(|err| format!("Error writing changelog file '{}': {}", path.display(), err))?;
+    } else if let Some(path) = output_path {
+        std::fs::write(&path, output)
+            .map_err(|err| format!("Error writing changelog file '{}': {}", path.display(), err))?;
+    } else {
+        println!("{}", output);
+    }
 
     Ok(())
 }
@@ -1,15 +1,32 @@
-//! Template represents parsed YAML config file
+//! Template represents a parsed YAML (or TOML) config file
 use indexmap::IndexMap;
+use regex::Regex;
 use serde_yaml::Value;
+use std::collections::HashMap;
 use std::error::Error;
 use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-/// Template represents parsed YAML config file
+/// Name of the project-local template file looked up by [`Template::load_layered`].
+const PROJECT_TEMPLATE_FILENAME: &str = ".mkchlog.yml";
+/// TOML counterpart of [`PROJECT_TEMPLATE_FILENAME`], tried when the YAML file isn't found.
+const PROJECT_TEMPLATE_FILENAME_TOML: &str = ".mkchlog.toml";
+/// Directory (under the user/global config dir) holding the user-level template.
+const USER_CONFIG_DIR: &str = "mkchlog";
+/// Name of the user/global template file looked up by [`Template::load_layered`].
+const USER_CONFIG_FILENAME: &str = "config.yml";
+/// TOML counterpart of [`USER_CONFIG_FILENAME`], tried when the YAML file isn't found.
+const USER_CONFIG_FILENAME_TOML: &str = "config.toml";
+
+/// Template represents a parsed YAML or TOML config file
 #[derive(Debug)]
 pub struct Template<T: Default> {
     changelog_template: ChangelogTemplate<T>,
     pub settings: Settings,
+    /// Which layer ([`Template::load_layered`]) each merged setting/section came from.
+    pub origin: ConfigOrigin,
 }
 
 /// Settings represent options that were set in YAML config file
@@ -17,6 +34,150 @@ pub struct Template<T: Default> {
 pub struct Settings {
     pub skip_commits_up_to: Option<String>,
     pub git_path: Option<std::path::PathBuf>,
+    /// Template rendered once before the body, whether or not any section has changes.
+    pub header_template: Option<String>,
+    /// Template rendered per-section/per-commit in place of the default Markdown layout.
+    pub body_template: Option<String>,
+    /// Template rendered once after the body, whether or not any section has changes.
+    pub footer_template: Option<String>,
+    /// Template for a single commit's link, e.g. `https://github.com/owner/repo/commit/{{ commit.id }}`.
+    /// Falls back to `{repo_url}/commit/{{ commit.id }}` when unset but `repo_url` is.
+    pub commit_url_template: Option<String>,
+    /// Base URL of the repo on its hosting platform, e.g. `https://github.com/owner/repo`.
+    pub repo_url: Option<String>,
+    /// How a commit's changelog information is expected to be written. Unset (the default)
+    /// means [`CommitStyle::Trailer`].
+    pub commit_style: Option<CommitStyle>,
+    /// Override of [`default_type_map`], used when `commit_style` is [`CommitStyle::Conventional`].
+    pub type_map: Option<HashMap<String, String>>,
+    /// Whether the changelog should be grouped into per-release sections delimited by the
+    /// repository's tags, instead of one flat block. Unset (the default) means `false`.
+    pub group_by_release: Option<bool>,
+    /// Restricts the tags considered for `group_by_release` to ones matching this glob
+    /// (e.g. `v*`). Unset (the default) considers every tag.
+    pub tag_pattern: Option<String>,
+    /// Directory of fragment files (one YAML/JSON changelog entry per unreleased change, e.g.
+    /// `.changelog/`), merged in alongside commits. Unset (the default) disables the feature.
+    pub fragments_dir: Option<std::path::PathBuf>,
+    /// Explicit `<rev>..<rev>` range of commits to process, taking precedence over `latest`
+    /// and `skip-commits-up-to`.
+    pub range: Option<String>,
+    /// Process commits since the most recent tag reachable from `HEAD`, taking precedence
+    /// over `skip-commits-up-to`. Unset (the default) means `false`.
+    pub latest: Option<bool>,
+    /// Only include commits at or after this date (passed straight to `git log --since`).
+    pub since: Option<String>,
+    /// Only include commits at or before this date (passed straight to `git log --until`).
+    pub until: Option<String>,
+    /// Restrict to commits touching this path (passed to `git log` as `-- <path>`), so a
+    /// multi-project monorepo can scope a project's changelog to its own subtree.
+    pub commit_path: Option<std::path::PathBuf>,
+}
+
+impl Settings {
+    /// Returns the effective commit URL template: an explicit `commit_url_template`,
+    /// or one derived from `repo_url` in the hosting platforms' usual `/commit/<hash>` form.
+    pub fn commit_url_template(&self) -> Option<String> {
+        self.commit_url_template.clone().or_else(|| {
+            self.repo_url
+                .as_deref()
+                .map(|url| format!("{}/commit/{{{{ commit.id }}}}", url.trim_end_matches('/')))
+        })
+    }
+
+    /// Returns the effective commit message style: an explicit `commit-style`, or
+    /// [`CommitStyle::Trailer`] otherwise.
+    pub fn commit_style(&self) -> CommitStyle {
+        self.commit_style.unwrap_or_default()
+    }
+
+    /// Returns the effective Conventional-Commit type-to-section map: an explicit
+    /// `type-map`, or [`default_type_map`] otherwise.
+    pub fn type_map(&self) -> HashMap<String, String> {
+        self.type_map.clone().unwrap_or_else(default_type_map)
+    }
+
+    /// Returns the effective release-grouping setting: an explicit `group-by-release`,
+    /// or `false` otherwise.
+    pub fn group_by_release(&self) -> bool {
+        self.group_by_release.unwrap_or_default()
+    }
+
+    /// Returns the effective "since latest tag" setting: an explicit `latest`, or `false`
+    /// otherwise.
+    pub fn latest(&self) -> bool {
+        self.latest.unwrap_or_default()
+    }
+}
+
+/// How a commit's changelog information is expected to be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CommitStyle {
+    /// The explicit `changelog:` YAML trailer in the commit message (the default).
+    #[default]
+    Trailer,
+    /// A Conventional Commits (`type(scope)!: summary`) subject line, with no `changelog:`
+    /// trailer required. The type is resolved to a section via `type-map`/[`Template::classify`]
+    /// and the scope, if any, is used as the project selector.
+    Conventional,
+}
+
+/// Built-in `type-map` used by [`CommitStyle::Conventional`] when no `type-map` override is
+/// configured, following the type names from the Conventional Commits/Angular conventions.
+pub fn default_type_map() -> HashMap<String, String> {
+    HashMap::from(
+        [
+            ("feat", "features"),
+            ("fix", "bug fixes"),
+            ("perf", "performance"),
+            ("docs", "doc"),
+            ("refactor", "refactor"),
+            ("test", "tests"),
+            ("style", "style"),
+            ("build", "build"),
+            ("ci", "ci"),
+            ("chore", "chore"),
+            ("revert", "revert"),
+        ]
+        .map(|(ty, section)| (ty.to_owned(), section.to_owned())),
+    )
+}
+
+/// A configuration layer considered by [`Template::load_layered`], in increasing
+/// precedence order (a later layer overrides an earlier one).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayer {
+    /// Built-in default: neither the user nor the project layer set the value.
+    BuiltIn,
+    /// User/global template (`$XDG_CONFIG_HOME/mkchlog/config.yml` or the platform config dir).
+    User,
+    /// Project template (the closest `.mkchlog.yml` found walking up from the current directory).
+    Project,
+}
+
+/// Records which [`ConfigLayer`] each merged setting/section came from, so a
+/// `--show-config-origin` style diagnostic can report where a value was set.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigOrigin {
+    pub skip_commits_up_to: Option<ConfigLayer>,
+    pub git_path: Option<ConfigLayer>,
+    pub header_template: Option<ConfigLayer>,
+    pub body_template: Option<ConfigLayer>,
+    pub footer_template: Option<ConfigLayer>,
+    pub commit_url_template: Option<ConfigLayer>,
+    pub repo_url: Option<ConfigLayer>,
+    pub commit_style: Option<ConfigLayer>,
+    pub type_map: Option<ConfigLayer>,
+    pub group_by_release: Option<ConfigLayer>,
+    pub tag_pattern: Option<ConfigLayer>,
+    pub fragments_dir: Option<ConfigLayer>,
+    pub range: Option<ConfigLayer>,
+    pub latest: Option<ConfigLayer>,
+    pub since: Option<ConfigLayer>,
+    pub until: Option<ConfigLayer>,
+    pub commit_path: Option<ConfigLayer>,
+    /// Keyed by the full section path (e.g. `security.vuln_fixes`).
+    pub sections: IndexMap<String, ConfigLayer>,
 }
 
 pub type ChangelogTemplate<T> = IndexMap<String, Section<T>>;
@@ -24,6 +185,10 @@ type Yaml = serde_yaml::Value;
 
 impl<T: Default> Template<T> {
     /// Parses the config (template) YAML file and returns the initialized template object.
+    ///
+    /// Any `include:` paths are resolved relative to the current directory. Use
+    /// [`Template::from_path`] when the template is read from a known file so that
+    /// includes resolve relative to that file's directory instead.
     pub fn new(mut file: impl Read) -> Result<Self, Box<dyn Error>> {
         let mut config_yml = String::new();
         file.read_to_string(&mut config_yml)?;
@@ -31,9 +196,61 @@ impl<T: Default> Template<T> {
         Self::from_str(&config_yml)
     }
 
+    /// Parses the config (template) file at `path`, resolving any `include:` paths
+    /// (top-level or inside a section) relative to `path`'s parent directory. Parsed as
+    /// TOML when `path`'s extension is `toml`, otherwise as YAML.
+    pub fn from_path(path: &std::path::Path) -> Result<Self, Box<dyn Error>> {
+        let config_raw = std::fs::read_to_string(path).map_err(|err| {
+            format!("Error reading config file '{}': {}", path.display(), err)
+        })?;
+
+        let base_dir = path
+            .parent()
+            .map(std::path::Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let mut ctx = IncludeContext {
+            base_dir,
+            chain: vec![],
+        };
+        if let Ok(canonical) = std::fs::canonicalize(path) {
+            ctx.chain.push(canonical);
+        }
+
+        let config = parse_value(&config_raw, path)?;
+        Self::from_value(config, ctx)
+    }
+
+    /// Parses `config_yml` as YAML, resolving `include:` paths relative to `ctx.base_dir`.
+    /// Used by [`FromStr::from_str`], where there's no file path to detect TOML from.
+    fn parse(config_yml: &str, ctx: IncludeContext) -> Result<Self, Box<dyn Error>> {
+        let config: Yaml = serde_yaml::from_str(config_yml)
+            .map_err(|err| format!("Error parsing config YAML file: {}", err))?;
+
+        Self::from_value(config, ctx)
+    }
+
+    /// Builds a [`Template`] from an already-parsed [`Yaml`] value, resolving `include:`
+    /// paths relative to `ctx.base_dir`. Shared by [`Template::parse`] (YAML strings) and
+    /// [`Template::from_path`] (YAML or TOML files, converted to the same [`Yaml`] shape by
+    /// [`parse_value`]), so both formats build an identical in-memory `Template`.
+    fn from_value(config: Yaml, mut ctx: IncludeContext) -> Result<Self, Box<dyn Error>> {
+        let settings = parse_settings(&config)?;
+
+        let mut template = Self {
+            changelog_template: ChangelogTemplate::new(),
+            settings,
+            origin: ConfigOrigin::default(),
+        };
+
+        template.parse_config(config, &mut ctx)?;
+
+        Ok(template)
+    }
+
     /// Validates template data extracted from the configuration (template) file
     /// and prepares data structure for storing changelog data.
-    fn parse_config(&mut self, yaml: Yaml) -> Result<(), Box<dyn Error>> {
+    fn parse_config(&mut self, yaml: Yaml, ctx: &mut IncludeContext) -> Result<(), Box<dyn Error>> {
         // parsing template YAML data
         let tmpl_sections_key = match yaml.get("sections") {
             Some(v) => v,
@@ -44,387 +261,2135 @@ impl<T: Default> Template<T> {
             .as_mapping()
             .ok_or("Malformed 'sections' key in config file")?;
 
-        for (sec, val) in tmpl_sections {
-            let sec = sec.as_str().ok_or("Invalid section")?.to_owned();
-            let val = val
-                .as_mapping()
-                .ok_or(format!("Invalid value in section '{}' in config file", sec))?;
-
-            let title = val
-                .get(&Value::from("title"))
-                .ok_or(format!(
-                    "Missing 'title' in section '{}' in config file",
-                    sec
-                ))?
-                .as_str()
-                .ok_or(format!(
-                    "Invalid 'title' in section '{}' in config file",
-                    sec
-                ))?;
-
-            let mut description = "".to_owned();
-            if let Some(descr) = val.get(&Value::from("description")) {
-                description = descr.as_str().unwrap_or("").to_string();
-            }
+        let sections = parse_sections(tmpl_sections, "", ctx)?;
+        validate_matchers(&sections)?;
+        self.changelog_template = sections;
+
+        Ok(())
+    }
+
+    /// Returns mutable reference to the data structure with initialized sections for storing changelog data.
+    pub fn data(&mut self) -> &mut ChangelogTemplate<T> {
+        &mut self.changelog_template
+    }
 
-            let mut section = Section {
-                title: title.to_string(),
-                description: description.to_string(),
+    /// Adds a new top-level section, returning a mutable handle for further edits.
+    pub fn add_section(&mut self, name: &str, title: &str, description: &str) -> &mut Section<T> {
+        self.changelog_template.insert(
+            name.to_owned(),
+            Section {
+                title: title.to_owned(),
+                description: description.to_owned(),
                 subsections: IndexMap::new(),
+                matcher: Matcher::default(),
                 changes: T::default(),
-            };
+            },
+        );
 
-            if let Some(subsections) = val.get(&Value::from("subsections")) {
-                let mut sub_section_map = IndexMap::<String, String>::new();
-                sub_section_map.insert("title".to_string(), title.to_string());
-
-                let subsections_map: Result<IndexMap<String, Section<T>>, String> = subsections
-                    .as_mapping()
-                    .ok_or(format!(
-                        "Invalid subsections format in section {} in config file",
-                        sec
-                    ))?
-                    .iter()
-                    .map(|(key, val)| {
-                        let subsection_name = key.as_str().ok_or(format!(
-                            "Invalid subsection in section '{}' in config file",
-                            sec
-                        ))?;
-
-                        let title = val
-                            .get("title")
-                            .ok_or(format!(
-                                "Missing 'title' in section '{}' in config file",
-                                subsection_name
-                            ))?
-                            .as_str()
-                            .ok_or(format!(
-                                "Invalid 'title' in section '{}' in config file",
-                                subsection_name
-                            ))?;
-
-                        let mut description = "";
-                        if let Some(descr) = val.get("description") {
-                            description = descr.as_str().unwrap_or("");
-                        }
-
-                        Ok((
-                            subsection_name.to_string(),
-                            Section {
-                                title: title.to_string(),
-                                description: description.to_string(),
-                                subsections: IndexMap::new(),
-                                changes: T::default(),
-                            },
-                        ))
-                    })
-                    .collect();
-                section.subsections = subsections_map?;
-            }
+        self.changelog_template
+            .get_mut(name)
+            .expect("section was just inserted")
+    }
 
-            self.changelog_template.insert(sec.to_string(), section);
-        }
+    /// Removes a top-level section, returning it if it existed.
+    pub fn remove_section(&mut self, name: &str) -> Option<Section<T>> {
+        self.changelog_template.shift_remove(name)
+    }
+
+    /// Renames a top-level section in place, preserving its position in the map.
+    pub fn rename_section(&mut self, name: &str, new_name: &str) -> Result<(), Box<dyn Error>> {
+        let index = self
+            .changelog_template
+            .get_index_of(name)
+            .ok_or(format!("Section '{}' not found", name))?;
+
+        let section = self
+            .changelog_template
+            .shift_remove(name)
+            .expect("index was just found");
+
+        self.changelog_template
+            .shift_insert(index, new_name.to_owned(), section);
 
         Ok(())
     }
 
-    /// Returns mutable reference to the data structure with initialized sections for storing changelog data.
-    pub fn data(&mut self) -> &mut ChangelogTemplate<T> {
-        &mut self.changelog_template
+    /// Returns a mutable handle to a top-level section for editing its `title`/`description`
+    /// or adding/removing subsections.
+    pub fn section_mut(&mut self, name: &str) -> Option<&mut Section<T>> {
+        self.changelog_template.get_mut(name)
     }
-}
 
-impl<T: Default> std::str::FromStr for Template<T> {
-    type Err = Box<dyn Error>;
+    /// Returns a mutable handle to a subsection for editing its `title`/`description`
+    /// or adding/removing its own nested subsections.
+    pub fn subsection_mut(&mut self, section: &str, subsection: &str) -> Option<&mut Section<T>> {
+        self.changelog_template
+            .get_mut(section)?
+            .subsections
+            .get_mut(subsection)
+    }
 
-    fn from_str(config_yml: &str) -> Result<Self, Self::Err> {
-        let config: Yaml = match serde_yaml::from_str(config_yml) {
-            Ok(config) => config,
-            Err(err) => return Err(format!("Error parsing config YAML file: {}", err).into()),
-        };
+    /// Serializes the template back into a YAML string that [`Template::from_str`]
+    /// can reparse: the `skip-commits-up-to`, `git-path`, `*-template`, `repo-url`,
+    /// `fragments-dir`, `range`, `since`, `until` and `commit-path` settings plus
+    /// sections and subsections with their `title`/`description`. `commit-style`,
+    /// `type-map`, `group-by-release`, `tag-pattern`, `latest` and sections' `match:`
+    /// blocks are not written, so they are lost on a round trip.
+    pub fn to_yaml(&self) -> String {
+        let mut root = serde_yaml::Mapping::new();
 
-        let skip_commits_up_to = config
-            .get("skip-commits-up-to")
-            .map(|v| {
-                v.as_str()
-                    .map(ToOwned::to_owned)
-                    .ok_or("'skip-commits-up-to' key must be a string")
-            })
-            .transpose()?;
-
-        let git_path = config
-            .get("git-path")
-            .map(|v| {
-                v.as_str()
-                    .map(std::path::PathBuf::from)
-                    .ok_or("'git-path' key must be a string")
-            })
-            .transpose()?;
+        if let Some(commit_id) = &self.settings.skip_commits_up_to {
+            root.insert(
+                Value::from("skip-commits-up-to"),
+                Value::from(commit_id.as_str()),
+            );
+        }
+
+        if let Some(git_path) = &self.settings.git_path {
+            root.insert(
+                Value::from("git-path"),
+                Value::from(git_path.to_string_lossy().into_owned()),
+            );
+        }
+
+        if let Some(header_template) = &self.settings.header_template {
+            root.insert(
+                Value::from("header-template"),
+                Value::from(header_template.as_str()),
+            );
+        }
+
+        if let Some(body_template) = &self.settings.body_template {
+            root.insert(
+                Value::from("body-template"),
+                Value::from(body_template.as_str()),
+            );
+        }
+
+        if let Some(footer_template) = &self.settings.footer_template {
+            root.insert(
+                Value::from("footer-template"),
+                Value::from(footer_template.as_str()),
+            );
+        }
+
+        if let Some(commit_url_template) = &self.settings.commit_url_template {
+            root.insert(
+                Value::from("commit-url-template"),
+                Value::from(commit_url_template.as_str()),
+            );
+        }
+
+        if let Some(repo_url) = &self.settings.repo_url {
+            root.insert(Value::from("repo-url"), Value::from(repo_url.as_str()));
+        }
+
+        if let Some(fragments_dir) = &self.settings.fragments_dir {
+            root.insert(
+                Value::from("fragments-dir"),
+                Value::from(fragments_dir.to_string_lossy().into_owned()),
+            );
+        }
+
+        if let Some(range) = &self.settings.range {
+            root.insert(Value::from("range"), Value::from(range.as_str()));
+        }
+
+        if let Some(since) = &self.settings.since {
+            root.insert(Value::from("since"), Value::from(since.as_str()));
+        }
+
+        if let Some(until) = &self.settings.until {
+            root.insert(Value::from("until"), Value::from(until.as_str()));
+        }
+
+        if let Some(commit_path) = &self.settings.commit_path {
+            root.insert(
+                Value::from("commit-path"),
+                Value::from(commit_path.to_string_lossy().into_owned()),
+            );
+        }
+
+        root.insert(
+            Value::from("sections"),
+            sections_to_yaml(&self.changelog_template),
+        );
+
+        serde_yaml::to_string(&Value::Mapping(root))
+            .expect("template always serializes to valid YAML")
+    }
 
+    /// Discovers and merges the project, user/global, and built-in template layers.
+    ///
+    /// Walks upward from the current directory looking for a project template
+    /// (`.mkchlog.yml`) and reads a user/global template from
+    /// `$XDG_CONFIG_HOME/mkchlog/config.yml` (falling back to the platform config
+    /// dir). Layers are merged with project overriding user overriding built-in
+    /// defaults: scalar [`Settings`] fields take the highest-precedence non-null
+    /// value, while `sections`/`subsections` merge by key so a layer can add new
+    /// sections or override an existing section's `title`/`description` without
+    /// redefining the whole map. Either layer is optional; a repository without a
+    /// `.mkchlog.yml` and a machine without a user config simply yields the
+    /// built-in (empty) defaults.
+    pub fn load_layered() -> Result<Self, Box<dyn Error>> {
         let mut template = Self {
             changelog_template: ChangelogTemplate::new(),
             settings: Settings {
-                skip_commits_up_to,
-                git_path,
+                skip_commits_up_to: None,
+                git_path: None,
+                header_template: None,
+                body_template: None,
+                footer_template: None,
+                commit_url_template: None,
+                repo_url: None,
+                commit_style: None,
+                type_map: None,
+                group_by_release: None,
+                tag_pattern: None,
+                fragments_dir: None,
+                range: None,
+                latest: None,
+                since: None,
+                until: None,
+                commit_path: None,
             },
+            origin: ConfigOrigin::default(),
+        };
+
+        if let Some(user_path) = user_config_path() {
+            if let Ok(contents) = std::fs::read_to_string(&user_path) {
+                let user_layer = Self::parse_layer(&contents, &user_path).map_err(|err| {
+                    format!(
+                        "Error reading user config '{}': {}",
+                        user_path.display(),
+                        err
+                    )
+                })?;
+                template.merge_layer(user_layer, ConfigLayer::User);
+            }
+        }
+
+        if let Some(project_path) = find_project_template() {
+            let contents = std::fs::read_to_string(&project_path).map_err(|err| {
+                format!(
+                    "Error reading project config '{}': {}",
+                    project_path.display(),
+                    err
+                )
+            })?;
+            let project_layer = Self::parse_layer(&contents, &project_path)?;
+            template.merge_layer(project_layer, ConfigLayer::Project);
+        }
+
+        Ok(template)
+    }
+
+    /// Parses a single configuration layer for [`Template::load_layered`]: unlike
+    /// [`Template::from_str`], the `sections` key is optional since a layer may
+    /// only contribute settings or a handful of shared sections. Parsed as TOML when
+    /// `path`'s extension is `toml`, otherwise as YAML.
+    fn parse_layer(config_raw: &str, path: &Path) -> Result<Self, Box<dyn Error>> {
+        let config: Yaml = parse_value(config_raw, path)?;
+
+        let settings = parse_settings(&config)?;
+
+        let mut template = Self {
+            changelog_template: ChangelogTemplate::new(),
+            settings,
+            origin: ConfigOrigin::default(),
         };
 
-        template.parse_config(config)?;
+        if config.get("sections").is_some() {
+            let mut ctx = IncludeContext {
+                base_dir: PathBuf::from("."),
+                chain: vec![],
+            };
+            template.parse_config(config, &mut ctx)?;
+        }
 
         Ok(template)
     }
+
+    /// Merges `other` into `self`, recording `layer` as the origin of every value it sets.
+    fn merge_layer(&mut self, other: Self, layer: ConfigLayer) {
+        if other.settings.skip_commits_up_to.is_some() {
+            self.settings.skip_commits_up_to = other.settings.skip_commits_up_to;
+            self.origin.skip_commits_up_to = Some(layer);
+        }
+
+        if other.settings.git_path.is_some() {
+            self.settings.git_path = other.settings.git_path;
+            self.origin.git_path = Some(layer);
+        }
+
+        if other.settings.header_template.is_some() {
+            self.settings.header_template = other.settings.header_template;
+            self.origin.header_template = Some(layer);
+        }
+
+        if other.settings.body_template.is_some() {
+            self.settings.body_template = other.settings.body_template;
+            self.origin.body_template = Some(layer);
+        }
+
+        if other.settings.footer_template.is_some() {
+            self.settings.footer_template = other.settings.footer_template;
+            self.origin.footer_template = Some(layer);
+        }
+
+        if other.settings.commit_url_template.is_some() {
+            self.settings.commit_url_template = other.settings.commit_url_template;
+            self.origin.commit_url_template = Some(layer);
+        }
+
+        if other.settings.repo_url.is_some() {
+            self.settings.repo_url = other.settings.repo_url;
+            self.origin.repo_url = Some(layer);
+        }
+
+        if other.settings.commit_style.is_some() {
+            self.settings.commit_style = other.settings.commit_style;
+            self.origin.commit_style = Some(layer);
+        }
+
+        if other.settings.type_map.is_some() {
+            self.settings.type_map = other.settings.type_map;
+            self.origin.type_map = Some(layer);
+        }
+
+        if other.settings.group_by_release.is_some() {
+            self.settings.group_by_release = other.settings.group_by_release;
+            self.origin.group_by_release = Some(layer);
+        }
+
+        if other.settings.tag_pattern.is_some() {
+            self.settings.tag_pattern = other.settings.tag_pattern;
+            self.origin.tag_pattern = Some(layer);
+        }
+
+        if other.settings.fragments_dir.is_some() {
+            self.settings.fragments_dir = other.settings.fragments_dir;
+            self.origin.fragments_dir = Some(layer);
+        }
+
+        if other.settings.range.is_some() {
+            self.settings.range = other.settings.range;
+            self.origin.range = Some(layer);
+        }
+
+        if other.settings.latest.is_some() {
+            self.settings.latest = other.settings.latest;
+            self.origin.latest = Some(layer);
+        }
+
+        if other.settings.since.is_some() {
+            self.settings.since = other.settings.since;
+            self.origin.since = Some(layer);
+        }
+
+        if other.settings.until.is_some() {
+            self.settings.until = other.settings.until;
+            self.origin.until = Some(layer);
+        }
+
+        if other.settings.commit_path.is_some() {
+            self.settings.commit_path = other.settings.commit_path;
+            self.origin.commit_path = Some(layer);
+        }
+
+        merge_section_maps(
+            &mut self.changelog_template,
+            other.changelog_template,
+            layer,
+            &mut self.origin.sections,
+            "",
+        );
+    }
+
+    /// Classifies a commit against every section's `match:` rule (depth-first, in
+    /// declaration order) and returns the path (e.g. `security.vuln_fixes`) of the
+    /// first section whose `types`, `scopes`, `regex`, or `breaking` claims it.
+    /// `commit_type` and `scope` are the Conventional-Commit type/scope (if the commit
+    /// parsed as one), `subject` is the commit's subject line, and `breaking` is whether
+    /// it's a Conventional Commits breaking change. Falls back to `fallback_section` when
+    /// nothing matches, if it names an existing section.
+    pub fn classify(
+        &self,
+        commit_type: Option<&str>,
+        scope: Option<&str>,
+        subject: &str,
+        breaking: bool,
+        fallback_section: Option<&str>,
+    ) -> Option<String> {
+        if let Some(path) = find_matching_section(
+            &self.changelog_template,
+            "",
+            commit_type,
+            scope,
+            subject,
+            breaking,
+        ) {
+            return Some(path);
+        }
+
+        fallback_section
+            .filter(|name| self.changelog_template.contains_key(*name))
+            .map(ToOwned::to_owned)
+    }
 }
 
-/// Data structure to store changelog section data
-#[derive(Debug, Clone, PartialEq)]
-pub struct Section<T: Default> {
-    pub title: String,
-    pub description: String,
-    pub subsections: IndexMap<String, Section<T>>,
-    pub changes: T,
+/// Recursively searches `sections` (depth-first, in declaration order) for the
+/// first one whose `match:` rule claims the commit.
+pub(crate) fn find_matching_section<T: Default>(
+    sections: &IndexMap<String, Section<T>>,
+    path_prefix: &str,
+    commit_type: Option<&str>,
+    scope: Option<&str>,
+    subject: &str,
+    breaking: bool,
+) -> Option<String> {
+    // a breaking change is routed to its dedicated section ahead of any `types`/`scopes`/
+    // `regex` rule, so e.g. a `feat!:` commit lands in "breaking" rather than "features"
+    if breaking {
+        if let Some(path) = find_breaking_section(sections, path_prefix) {
+            return Some(path);
+        }
+    }
+
+    for (name, section) in sections {
+        let path = join_path(path_prefix, name);
+
+        let type_matches =
+            commit_type.is_some_and(|t| section.matcher.types.iter().any(|ty| ty == t));
+        let scope_matches = scope.is_some_and(|s| section.matcher.scopes.iter().any(|sc| sc == s));
+        let regex_matches = section
+            .matcher
+            .regex
+            .as_ref()
+            .is_some_and(|re| re.is_match(subject));
+
+        if type_matches || scope_matches || regex_matches {
+            return Some(path);
+        }
+
+        if let Some(found) = find_matching_section(
+            &section.subsections,
+            &path,
+            commit_type,
+            scope,
+            subject,
+            breaking,
+        ) {
+            return Some(found);
+        }
+    }
+
+    None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Template;
-    use crate::changelog::Changes;
-    use std::io::Cursor;
+/// Depth-first search for the first section whose `match: { breaking: true }` claims every
+/// breaking change, used by [`find_matching_section`] to give that rule top priority.
+fn find_breaking_section<T: Default>(
+    sections: &IndexMap<String, Section<T>>,
+    path_prefix: &str,
+) -> Option<String> {
+    for (name, section) in sections {
+        let path = join_path(path_prefix, name);
+
+        if section.matcher.breaking {
+            return Some(path);
+        }
+
+        if let Some(found) = find_breaking_section(&section.subsections, &path) {
+            return Some(found);
+        }
+    }
+
+    None
+}
+
+/// Returns the path to the project template by walking upward from the current
+/// directory, mirroring how git finds `.git` or `.gitconfig`.
+fn find_project_template() -> Option<PathBuf> {
+    let mut dir = std::env::current_dir().ok()?;
+    loop {
+        let candidate = dir.join(PROJECT_TEMPLATE_FILENAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        let toml_candidate = dir.join(PROJECT_TEMPLATE_FILENAME_TOML);
+        if toml_candidate.is_file() {
+            return Some(toml_candidate);
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Returns the path to the user/global template: `$XDG_CONFIG_HOME/mkchlog/config.yml` (or
+/// `config.toml`, tried if the YAML file doesn't exist), falling back to the platform config
+/// dir (`$HOME/.config` on Unix, `%APPDATA%` on Windows).
+fn user_config_path() -> Option<PathBuf> {
+    let config_dir = if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        PathBuf::from(xdg).join(USER_CONFIG_DIR)
+    } else {
+        #[cfg(windows)]
+        let base = std::env::var_os("APPDATA").map(PathBuf::from)?;
+        #[cfg(not(windows))]
+        let base = std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))?;
+
+        base.join(USER_CONFIG_DIR)
+    };
+
+    let yml_path = config_dir.join(USER_CONFIG_FILENAME);
+    if yml_path.is_file() {
+        return Some(yml_path);
+    }
+
+    let toml_path = config_dir.join(USER_CONFIG_FILENAME_TOML);
+    if toml_path.is_file() {
+        return Some(toml_path);
+    }
+
+    Some(yml_path)
+}
+
+/// Merges `overlay` into `base` by key: an existing section has its `title`/`description`
+/// overridden and its subsections merged recursively, while a new key is inserted outright.
+fn merge_section_maps<T: Default>(
+    base: &mut IndexMap<String, Section<T>>,
+    overlay: IndexMap<String, Section<T>>,
+    layer: ConfigLayer,
+    origin: &mut IndexMap<String, ConfigLayer>,
+    path_prefix: &str,
+) {
+    for (name, overlay_section) in overlay {
+        let path = if path_prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{}.{}", path_prefix, name)
+        };
+
+        match base.get_mut(&name) {
+            Some(existing) => {
+                existing.title = overlay_section.title;
+                if !overlay_section.description.is_empty() {
+                    existing.description = overlay_section.description;
+                }
+                if !overlay_section.matcher.is_empty() {
+                    existing.matcher = overlay_section.matcher;
+                }
+                merge_section_maps(
+                    &mut existing.subsections,
+                    overlay_section.subsections,
+                    layer,
+                    origin,
+                    &path,
+                );
+            }
+            None => {
+                base.insert(name, overlay_section);
+            }
+        }
+
+        origin.insert(path, layer);
+    }
+}
+
+impl<T: Default> std::str::FromStr for Template<T> {
+    type Err = Box<dyn Error>;
+
+    fn from_str(config_yml: &str) -> Result<Self, Self::Err> {
+        let ctx = IncludeContext {
+            base_dir: PathBuf::from("."),
+            chain: vec![],
+        };
+
+        Self::parse(config_yml, ctx)
+    }
+}
+
+/// Tracks state needed to resolve `include:` directives: the directory included
+/// paths are relative to, and the chain of canonicalized paths visited so far
+/// (used to detect include cycles).
+struct IncludeContext {
+    base_dir: PathBuf,
+    chain: Vec<PathBuf>,
+}
+
+/// Parses `content` into the [`Yaml`] shape every section/settings parser in this module
+/// works from: directly as YAML, or (when `path`'s extension is `toml`) as TOML first,
+/// converted through [`serde_yaml::to_value`] so the rest of the parsing pipeline doesn't
+/// need to know which format the file was actually written in.
+fn parse_value(content: &str, path: &Path) -> Result<Yaml, Box<dyn Error>> {
+    if path.extension().and_then(|ext| ext.to_str()) == Some("toml") {
+        let toml_value: toml::Value = toml::from_str(content)
+            .map_err(|err| format!("Error parsing TOML config file '{}': {}", path.display(), err))?;
+
+        serde_yaml::to_value(toml_value).map_err(|err| {
+            format!(
+                "Error converting TOML config file '{}' for parsing: {}",
+                path.display(),
+                err
+            )
+            .into()
+        })
+    } else {
+        serde_yaml::from_str(content)
+            .map_err(|err| format!("Error parsing config file '{}': {}", path.display(), err).into())
+    }
+}
+
+/// Reads `skip-commits-up-to`/`git-path`/`*-template`/`commit-style`/`type-map` out of a
+/// parsed config layer.
+fn parse_settings(config: &Yaml) -> Result<Settings, Box<dyn Error>> {
+    let skip_commits_up_to = config
+        .get("skip-commits-up-to")
+        .map(|v| {
+            v.as_str()
+                .map(ToOwned::to_owned)
+                .ok_or("'skip-commits-up-to' key must be a string")
+        })
+        .transpose()?;
+
+    let git_path = config
+        .get("git-path")
+        .map(|v| {
+            v.as_str()
+                .map(std::path::PathBuf::from)
+                .ok_or("'git-path' key must be a string")
+        })
+        .transpose()?;
+
+    let header_template = parse_template_setting(config, "header-template")?;
+    let body_template = parse_template_setting(config, "body-template")?;
+    let footer_template = parse_template_setting(config, "footer-template")?;
+    let commit_url_template = parse_template_setting(config, "commit-url-template")?;
+    let repo_url = parse_template_setting(config, "repo-url")?;
+
+    let commit_style = config
+        .get("commit-style")
+        .map(|v| {
+            let style = v.as_str().ok_or("'commit-style' key must be a string")?;
+            match style {
+                "trailer" => Ok(CommitStyle::Trailer),
+                "conventional" => Ok(CommitStyle::Conventional),
+                other => Err(format!(
+                    "'commit-style' must be 'trailer' or 'conventional', got '{}'",
+                    other
+                )),
+            }
+        })
+        .transpose()?;
+
+    let type_map = config
+        .get("type-map")
+        .map(|v| {
+            let mapping = v.as_mapping().ok_or("'type-map' key must be a mapping")?;
+            mapping
+                .iter()
+                .map(|(k, v)| {
+                    let k = k.as_str().ok_or("'type-map' keys must be strings")?;
+                    let v = v.as_str().ok_or("'type-map' values must be strings")?;
+                    Ok((k.to_owned(), v.to_owned()))
+                })
+                .collect::<Result<HashMap<String, String>, &str>>()
+        })
+        .transpose()?;
+
+    let group_by_release = config
+        .get("group-by-release")
+        .map(|v| {
+            v.as_bool()
+                .ok_or("'group-by-release' key must be a boolean")
+        })
+        .transpose()?;
+
+    let tag_pattern = config
+        .get("tag-pattern")
+        .map(|v| {
+            v.as_str()
+                .map(ToOwned::to_owned)
+                .ok_or("'tag-pattern' key must be a string")
+        })
+        .transpose()?;
+
+    let fragments_dir = config
+        .get("fragments-dir")
+        .map(|v| {
+            v.as_str()
+                .map(std::path::PathBuf::from)
+                .ok_or("'fragments-dir' key must be a string")
+        })
+        .transpose()?;
+
+    let range = config
+        .get("range")
+        .map(|v| v.as_str().map(ToOwned::to_owned).ok_or("'range' key must be a string"))
+        .transpose()?;
+
+    let latest = config
+        .get("latest")
+        .map(|v| v.as_bool().ok_or("'latest' key must be a boolean"))
+        .transpose()?;
+
+    let since = config
+        .get("since")
+        .map(|v| v.as_str().map(ToOwned::to_owned).ok_or("'since' key must be a string"))
+        .transpose()?;
+
+    let until = config
+        .get("until")
+        .map(|v| v.as_str().map(ToOwned::to_owned).ok_or("'until' key must be a string"))
+        .transpose()?;
+
+    let commit_path = config
+        .get("commit-path")
+        .map(|v| {
+            v.as_str()
+                .map(std::path::PathBuf::from)
+                .ok_or("'commit-path' key must be a string")
+        })
+        .transpose()?;
+
+    Ok(Settings {
+        skip_commits_up_to,
+        git_path,
+        header_template,
+        body_template,
+        footer_template,
+        commit_url_template,
+        repo_url,
+        commit_style,
+        type_map,
+        group_by_release,
+        tag_pattern,
+        fragments_dir,
+        range,
+        latest,
+        since,
+        until,
+        commit_path,
+    })
+}
+
+/// Reads an optional string-valued template setting (e.g. `body-template`) out of a
+/// parsed config layer.
+fn parse_template_setting(config: &Yaml, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+    config
+        .get(key)
+        .map(|v| {
+            v.as_str()
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| format!("'{}' key must be a string", key).into())
+        })
+        .transpose()
+}
+
+/// Parses a `sections:`/`subsections:` mapping at `path_prefix` into an ordered map of
+/// [`Section`]s, splicing in any `include:` entry at the position it appears so that
+/// a locally-defined key always wins over one pulled in from an included fragment.
+fn parse_sections<T: Default>(
+    mapping: &serde_yaml::Mapping,
+    path_prefix: &str,
+    ctx: &mut IncludeContext,
+) -> Result<IndexMap<String, Section<T>>, Box<dyn Error>> {
+    let mut sections = IndexMap::<String, Section<T>>::new();
+
+    for (key, val) in mapping {
+        let name = key.as_str().ok_or("Invalid section")?;
+
+        if name == "include" {
+            let included = resolve_include(val, path_prefix, ctx)?;
+            for (inc_name, inc_section) in included {
+                sections.entry(inc_name).or_insert(inc_section);
+            }
+            continue;
+        }
+
+        let path = join_path(path_prefix, name);
+        let val = val.as_mapping().ok_or(format!(
+            "Invalid value in section '{}' in config file",
+            path
+        ))?;
+
+        let section = parse_section_body(&path, val, ctx)?;
+        sections.insert(name.to_string(), section);
+    }
+
+    Ok(sections)
+}
+
+/// Parses a single section (or subsection) at `path`, recursing into any nested
+/// `subsections` so groupings of arbitrary depth (category -> subsystem -> component) are preserved.
+fn parse_section_body<T: Default>(
+    path: &str,
+    val: &serde_yaml::Mapping,
+    ctx: &mut IncludeContext,
+) -> Result<Section<T>, Box<dyn Error>> {
+    let title = val
+        .get(&Value::from("title"))
+        .ok_or(format!(
+            "Missing 'title' in section '{}' in config file",
+            path
+        ))?
+        .as_str()
+        .ok_or(format!(
+            "Invalid 'title' in section '{}' in config file",
+            path
+        ))?;
+
+    let mut description = "".to_owned();
+    if let Some(descr) = val.get(&Value::from("description")) {
+        description = descr.as_str().unwrap_or("").to_string();
+    }
+
+    let mut subsections = IndexMap::<String, Section<T>>::new();
+
+    if let Some(subs) = val.get(&Value::from("subsections")) {
+        let subs = subs.as_mapping().ok_or(format!(
+            "Invalid subsections format in section '{}' in config file",
+            path
+        ))?;
+
+        subsections = parse_sections(subs, path, ctx)?;
+    }
+
+    let matcher = parse_matcher(path, val)?;
+
+    Ok(Section {
+        title: title.to_string(),
+        description,
+        subsections,
+        matcher,
+        changes: T::default(),
+    })
+}
+
+/// Parses a section's optional `match:` block, compiling its `regex` (if any).
+fn parse_matcher(path: &str, val: &serde_yaml::Mapping) -> Result<Matcher, Box<dyn Error>> {
+    let raw = match val.get(&Value::from("match")) {
+        Some(v) => v,
+        None => return Ok(Matcher::default()),
+    };
+
+    let raw = raw.as_mapping().ok_or(format!(
+        "Invalid 'match' block in section '{}' in config file",
+        path
+    ))?;
+
+    let types = parse_match_string_list(raw, "types", path)?;
+    let scopes = parse_match_string_list(raw, "scopes", path)?;
+
+    let regex = match raw.get(&Value::from("regex")) {
+        Some(v) => {
+            let pattern = v.as_str().ok_or(format!(
+                "'match.regex' in section '{}' must be a string",
+                path
+            ))?;
+            let regex = Regex::new(pattern)
+                .map_err(|err| format!("Invalid 'match.regex' in section '{}': {}", path, err))?;
+            Some(regex)
+        }
+        None => None,
+    };
+
+    let breaking = match raw.get(&Value::from("breaking")) {
+        Some(v) => v.as_bool().ok_or(format!(
+            "'match.breaking' in section '{}' must be a boolean",
+            path
+        ))?,
+        None => false,
+    };
+
+    Ok(Matcher {
+        types,
+        scopes,
+        regex,
+        breaking,
+    })
+}
+
+/// Parses a string-list key (`types`/`scopes`) of a section's `match:` block.
+fn parse_match_string_list(
+    mapping: &serde_yaml::Mapping,
+    key: &str,
+    path: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let v = match mapping.get(&Value::from(key)) {
+        Some(v) => v,
+        None => return Ok(vec![]),
+    };
+
+    let seq = v.as_sequence().ok_or(format!(
+        "'match.{}' in section '{}' must be a list of strings",
+        key, path
+    ))?;
+
+    seq.iter()
+        .map(|item| {
+            item.as_str().map(str::to_owned).ok_or_else(|| {
+                format!(
+                    "'match.{}' in section '{}' must be a list of strings",
+                    key, path
+                )
+                .into()
+            })
+        })
+        .collect()
+}
+
+/// Checks that no two sections (at any depth) claim the same Conventional-Commit
+/// `type` via their `match:` block, since [`Template::classify`] picks the first match.
+fn validate_matchers<T: Default>(
+    sections: &IndexMap<String, Section<T>>,
+) -> Result<(), Box<dyn Error>> {
+    let mut claims = HashMap::new();
+    collect_type_claims(sections, "", &mut claims)
+}
+
+/// Recursively records each section's claimed `match.types` into `claims`, erroring
+/// out on the first type claimed by more than one section.
+fn collect_type_claims<T: Default>(
+    sections: &IndexMap<String, Section<T>>,
+    path_prefix: &str,
+    claims: &mut HashMap<String, String>,
+) -> Result<(), Box<dyn Error>> {
+    for (name, section) in sections {
+        let path = join_path(path_prefix, name);
+
+        for ty in &section.matcher.types {
+            if let Some(existing) = claims.get(ty) {
+                return Err(format!(
+                    "section '{}' and '{}' both match type '{}'",
+                    existing, path, ty
+                )
+                .into());
+            }
+            claims.insert(ty.clone(), path.clone());
+        }
+
+        collect_type_claims(&section.subsections, &path, claims)?;
+    }
+
+    Ok(())
+}
+
+/// Joins a section path prefix and a key, e.g. `join_path("security", "vuln_fixes")`
+/// returns `"security.vuln_fixes"`.
+fn join_path(path_prefix: &str, name: &str) -> String {
+    if path_prefix.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path_prefix, name)
+    }
+}
+
+/// Resolves an `include:` value (a path or a list of paths) at `path_prefix`,
+/// reading and parsing each fragment and merging their sections in order.
+fn resolve_include<T: Default>(
+    val: &Yaml,
+    path_prefix: &str,
+    ctx: &mut IncludeContext,
+) -> Result<IndexMap<String, Section<T>>, Box<dyn Error>> {
+    let mut paths = vec![];
+
+    if let Some(s) = val.as_str() {
+        paths.push(s.to_owned());
+    } else if let Some(seq) = val.as_sequence() {
+        for v in seq {
+            let s = v.as_str().ok_or(format!(
+                "Invalid 'include' entry in section '{}' in config file: expected a path string",
+                path_prefix
+            ))?;
+            paths.push(s.to_owned());
+        }
+    } else {
+        return Err(format!(
+            "Invalid 'include' value in section '{}' in config file: expected a path or list of paths",
+            path_prefix
+        )
+        .into());
+    }
+
+    let mut sections = IndexMap::<String, Section<T>>::new();
+    for path in paths {
+        let included = read_include_file(&path, ctx)?;
+        for (name, section) in included {
+            sections.entry(name).or_insert(section);
+        }
+    }
+
+    Ok(sections)
+}
+
+/// Reads and parses the `sections:` mapping of an included template fragment,
+/// resolving `rel_path` relative to `ctx.base_dir` and detecting include cycles.
+fn read_include_file<T: Default>(
+    rel_path: &str,
+    ctx: &mut IncludeContext,
+) -> Result<IndexMap<String, Section<T>>, Box<dyn Error>> {
+    let path = ctx.base_dir.join(rel_path);
+
+    let canonical = std::fs::canonicalize(&path).map_err(|err| {
+        format!(
+            "Error reading included template '{}': {}",
+            path.display(),
+            err
+        )
+    })?;
+
+    if let Some(pos) = ctx.chain.iter().position(|p| *p == canonical) {
+        let mut cycle: Vec<String> = ctx.chain[pos..]
+            .iter()
+            .map(|p| {
+                p.file_name().map_or_else(
+                    || p.display().to_string(),
+                    |n| n.to_string_lossy().into_owned(),
+                )
+            })
+            .collect();
+        cycle.push(canonical.file_name().map_or_else(
+            || canonical.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        ));
+
+        return Err(format!("include cycle detected: {}", cycle.join(" -> ")).into());
+    }
+
+    let contents = std::fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "Error reading included template '{}': {}",
+            path.display(),
+            err
+        )
+    })?;
+
+    let yaml: Yaml = parse_value(&contents, &path).map_err(|err| {
+        format!("Error parsing included template '{}': {}", path.display(), err)
+    })?;
+
+    let tmpl_sections_key = yaml.get("sections").ok_or(format!(
+        "Missing 'sections' key in included template '{}'",
+        path.display()
+    ))?;
+    let tmpl_sections = tmpl_sections_key.as_mapping().ok_or(format!(
+        "Malformed 'sections' key in included template '{}'",
+        path.display()
+    ))?;
+
+    let included_base_dir = path
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let saved_base_dir = std::mem::replace(&mut ctx.base_dir, included_base_dir);
+    ctx.chain.push(canonical);
+
+    let result = parse_sections(tmpl_sections, "", ctx);
+
+    ctx.chain.pop();
+    ctx.base_dir = saved_base_dir;
+
+    result
+}
+
+/// Data structure to store changelog section data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section<T: Default> {
+    pub title: String,
+    pub description: String,
+    pub subsections: IndexMap<String, Section<T>>,
+    /// Commit-routing rule declared by this section's `match:` block, if any.
+    pub matcher: Matcher,
+    pub changes: T,
+}
+
+/// Commit-routing rule declared by a section's `match:` block: Conventional-Commit
+/// types/scopes, a regex matched against the commit subject line, and/or whether the
+/// commit is a Conventional Commits breaking change.
+#[derive(Debug, Clone, Default)]
+pub struct Matcher {
+    /// Conventional-Commit types (e.g. `feat`, `fix`) this section claims.
+    pub types: Vec<String>,
+    /// Conventional-Commit scopes this section claims.
+    pub scopes: Vec<String>,
+    /// Regex matched against the commit subject line.
+    pub regex: Option<Regex>,
+    /// When `true`, this section claims every breaking change (a `!` before the `:`, or a
+    /// `BREAKING CHANGE:`/`BREAKING-CHANGE:` footer), regardless of its type/scope. Lets a
+    /// template route breaking changes into a dedicated section ahead of `type-map`.
+    pub breaking: bool,
+}
+
+impl Matcher {
+    /// Returns `true` if this section declared no `match:` rule at all.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.scopes.is_empty() && self.regex.is_none() && !self.breaking
+    }
+}
+
+impl PartialEq for Matcher {
+    fn eq(&self, other: &Self) -> bool {
+        self.types == other.types
+            && self.scopes == other.scopes
+            && self.regex.as_ref().map(Regex::as_str) == other.regex.as_ref().map(Regex::as_str)
+            && self.breaking == other.breaking
+    }
+}
+
+impl<T: Default> Section<T> {
+    /// Adds a new subsection, returning a mutable handle for further edits.
+    pub fn add_subsection(
+        &mut self,
+        name: &str,
+        title: &str,
+        description: &str,
+    ) -> &mut Section<T> {
+        self.subsections.insert(
+            name.to_owned(),
+            Section {
+                title: title.to_owned(),
+                description: description.to_owned(),
+                subsections: IndexMap::new(),
+                matcher: Matcher::default(),
+                changes: T::default(),
+            },
+        );
+
+        self.subsections
+            .get_mut(name)
+            .expect("subsection was just inserted")
+    }
+
+    /// Removes a subsection, returning it if it existed.
+    pub fn remove_subsection(&mut self, name: &str) -> Option<Section<T>> {
+        self.subsections.shift_remove(name)
+    }
+}
+
+/// Builds the `sections:`/`subsections:` YAML mapping for [`Template::to_yaml`].
+fn sections_to_yaml<T: Default>(sections: &IndexMap<String, Section<T>>) -> Value {
+    let mut mapping = serde_yaml::Mapping::new();
+
+    for (name, section) in sections {
+        let mut entry = serde_yaml::Mapping::new();
+        entry.insert(Value::from("title"), Value::from(section.title.as_str()));
+
+        if !section.description.is_empty() {
+            entry.insert(
+                Value::from("description"),
+                Value::from(section.description.as_str()),
+            );
+        }
+
+        if !section.subsections.is_empty() {
+            entry.insert(
+                Value::from("subsections"),
+                sections_to_yaml(&section.subsections),
+            );
+        }
+
+        mapping.insert(Value::from(name.as_str()), Value::Mapping(entry));
+    }
+
+    Value::Mapping(mapping)
+}
+
+impl<T: Default> std::fmt::Display for Template<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_yaml())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use crate::changelog::Changes;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    pub struct FileReaderMock {
+        content: Cursor<String>,
+    }
+
+    impl FileReaderMock {
+        pub fn new(content: &str) -> Self {
+            Self {
+                content: Cursor::new(content.to_owned()),
+            }
+        }
+    }
+
+    impl std::io::Read for FileReaderMock {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.content.read(buf)
+        }
+    }
+
+    #[test]
+    fn template_valid_yaml() {
+        use super::Matcher;
+        use super::Section;
+        use indexmap::IndexMap;
+
+        let f = FileReaderMock::new(
+            "\
+skip-commits-up-to: bc58e6bf2cf640d46aa832e297d0f215f76dfce0
+
+sections:
+    # section identifier selected by project maintainer
+    security:
+        # The header presented to the user
+        title: Security
+        # desctiption is optional and will appear above changes
+        description: This section contains very important security-related changes.
+        subsections:
+            vuln_fixes:
+                title: Fixed vulnerabilities
+    features:
+        # some comment
+        title: New features
+    bug_fixes:
+        title: Fixed bugs
+    breaking:
+        title: Breaking changes
+    perf:
+        title: Performance improvements
+    dev:
+        title: Development
+        description: Internal development changes
+",
+        );
+
+        let res = Template::new(f);
+        assert!(res.is_ok());
+
+        let mut template = res.unwrap();
+
+        // check for correctly parsed settings
+        let settings = &template.settings;
+
+        assert_eq!(
+            settings.skip_commits_up_to.as_ref().unwrap(),
+            "bc58e6bf2cf640d46aa832e297d0f215f76dfce0"
+        );
+
+        // check if parsed template has correct format
+        let template_data = template.data();
+
+        let exp_keys = template_data.keys().collect::<Vec<_>>();
+        assert_eq!(exp_keys.len(), 6);
+        assert_eq!(
+            exp_keys,
+            vec![
+                "security",
+                "features",
+                "bug_fixes",
+                "breaking",
+                "perf",
+                "dev",
+            ]
+        );
+
+        let exp_sections = template_data.values().cloned().collect::<Vec<_>>();
+        assert_eq!(exp_sections.len(), 6);
+
+        // 'security' section with subsection
+        let mut subsecs = IndexMap::new();
+        subsecs.insert(
+            "vuln_fixes".to_owned(),
+            Section {
+                title: "Fixed vulnerabilities".to_owned(),
+                description: "".to_owned(),
+                subsections: IndexMap::new(),
+                matcher: Matcher::default(),
+                changes: Changes::default(),
+            },
+        );
+        assert_eq!(
+            exp_sections[0],
+            Section {
+                title: "Security".to_owned(),
+                description: "This section contains very important security-related changes."
+                    .to_owned(),
+                subsections: subsecs,
+                matcher: Matcher::default(),
+                changes: Changes::default(),
+            }
+        );
+
+        // 'features' section
+        assert_eq!(
+            exp_sections[1],
+            Section {
+                title: "New features".to_owned(),
+                description: "".to_owned(),
+                subsections: IndexMap::new(),
+                matcher: Matcher::default(),
+                changes: Changes::default(),
+            }
+        );
+
+        // 'dev' section
+        assert_eq!(
+            exp_sections[5],
+            Section {
+                title: "Development".to_owned(),
+                description: "Internal development changes".to_owned(),
+                subsections: IndexMap::new(),
+                matcher: Matcher::default(),
+                changes: Changes::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn template_nested_subsections() {
+        use super::Matcher;
+        use super::Section;
+        use indexmap::IndexMap;
+
+        let f = FileReaderMock::new(
+            "\
+sections:
+    security:
+        title: Security
+        subsections:
+            vuln_fixes:
+                title: Fixed vulnerabilities
+                subsections:
+                    regressions:
+                        title: Regressions
+                        description: Previously fixed vulnerabilities that reappeared.
+",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_ok());
+
+        let mut template = res.unwrap();
+        let template_data = template.data();
+
+        let security = template_data.get("security").unwrap();
+        let vuln_fixes = security.subsections.get("vuln_fixes").unwrap();
+        let regressions = vuln_fixes.subsections.get("regressions").unwrap();
+
+        assert_eq!(
+            regressions,
+            &Section {
+                title: "Regressions".to_owned(),
+                description: "Previously fixed vulnerabilities that reappeared.".to_owned(),
+                subsections: IndexMap::new(),
+                matcher: Matcher::default(),
+                changes: Changes::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn template_missing_title_in_nested_subsection() {
+        let f = FileReaderMock::new(
+            "\
+sections:
+    security:
+        title: Security
+        subsections:
+            vuln_fixes:
+                title: Fixed vulnerabilities
+                subsections:
+                    regressions:
+                        description: Missing a title
+",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Missing 'title' in section 'security.vuln_fixes.regressions' in config file"
+        );
+    }
+
+    #[test]
+    fn template_malformed_yaml() {
+        let f = FileReaderMock::new(
+            "\
+    features: title: New features
+    perf:
+        title: Performance improvements",
+        );
+        let res = Template::<Changes>::new(f);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("Error parsing config YAML file:"));
+    }
+
+    #[test]
+    fn template_missing_sections_key() {
+        let f = FileReaderMock::new(
+            "\
+features:
+    title: New features
+perf:
+    title: Performance improvements",
+        );
+        let res = Template::<Changes>::new(f);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("Missing 'sections' key in config file"));
+    }
+
+    #[test]
+    fn template_misspelled_sections_key() {
+        let f = FileReaderMock::new(
+            "\
+sekciones:
+    features:
+        title: New features
+    perf:
+        title: Performance improvements",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Missing 'sections' key in config file"
+        );
+    }
+
+    #[test]
+    fn template_malformed_sections_key() {
+        let f = FileReaderMock::new(
+            "\
+sections: [whatever]
+",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Malformed 'sections' key in config file"
+        );
+    }
+
+    #[test]
+    fn template_missing_title_in_section() {
+        let f = FileReaderMock::new(
+            "\
+sections:
+    features:
+        description: New features
+    perf:
+        title: Performance improvements",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Missing 'title' in section 'features' in config file"
+        );
+    }
+
+    #[test]
+    fn template_invalid_title_in_section() {
+        let f = FileReaderMock::new(
+            "\
+sections:
+    features:
+        title: New features
+    perf:
+        title: [Performance improvements]",
+        );
+
+        let res = Template::<Changes>::new(f);
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Invalid 'title' in section 'perf' in config file"
+        );
+    }
+
+    #[test]
+    fn template_merge_layer_overrides_settings_and_adds_sections() {
+        use super::ConfigLayer;
+
+        let mut template = Template::<Changes>::from_str(
+            "\
+skip-commits-up-to: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        let overlay = Template::<Changes>::parse_layer(
+            "\
+skip-commits-up-to: bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+sections:
+    features:
+        title: Features
+    dev:
+        title: Development
+",
+            std::path::Path::new(".mkchlog.yml"),
+        )
+        .unwrap();
+
+        template.merge_layer(overlay, ConfigLayer::Project);
+
+        assert_eq!(
+            template.settings.skip_commits_up_to.as_deref(),
+            Some("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb")
+        );
+        assert_eq!(
+            template.origin.skip_commits_up_to,
+            Some(ConfigLayer::Project)
+        );
+
+        let data = template.data();
+        assert_eq!(data.get("features").unwrap().title, "Features");
+        assert_eq!(data.get("dev").unwrap().title, "Development");
+        assert_eq!(
+            template.origin.sections.get("features"),
+            Some(&ConfigLayer::Project)
+        );
+        assert_eq!(
+            template.origin.sections.get("dev"),
+            Some(&ConfigLayer::Project)
+        );
+    }
+
+    #[test]
+    fn template_commit_style_and_type_map_parsing() {
+        use super::CommitStyle;
+
+        let template = Template::<Changes>::from_str(
+            "\
+commit-style: conventional
+type-map:
+    feat: enhancements
+sections:
+    enhancements:
+        title: Enhancements
+",
+        )
+        .unwrap();
+
+        assert_eq!(template.settings.commit_style(), CommitStyle::Conventional);
+        assert_eq!(
+            template.settings.type_map().get("feat").map(String::as_str),
+            Some("enhancements")
+        );
+    }
+
+    #[test]
+    fn template_commit_style_and_type_map_default_when_unset() {
+        use super::{default_type_map, CommitStyle};
+
+        let template = Template::<Changes>::from_str(
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert_eq!(template.settings.commit_style(), CommitStyle::Trailer);
+        assert_eq!(template.settings.type_map(), default_type_map());
+    }
+
+    #[test]
+    fn template_invalid_commit_style() {
+        let res = Template::<Changes>::from_str(
+            "\
+commit-style: yolo
+sections:
+    features:
+        title: New features
+",
+        );
+
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "'commit-style' must be 'trailer' or 'conventional', got 'yolo'"
+        );
+    }
+
+    #[test]
+    fn template_group_by_release_parsing() {
+        let template = Template::<Changes>::from_str(
+            "\
+group-by-release: true
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert!(template.settings.group_by_release());
+    }
+
+    #[test]
+    fn template_group_by_release_default_when_unset() {
+        let template = Template::<Changes>::from_str(
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert!(!template.settings.group_by_release());
+    }
+
+    #[test]
+    fn template_fragments_dir_parsing() {
+        let template = Template::<Changes>::from_str(
+            "\
+fragments-dir: .changelog
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.settings.fragments_dir,
+            Some(std::path::PathBuf::from(".changelog"))
+        );
+    }
+
+    #[test]
+    fn template_fragments_dir_unset_by_default() {
+        let template = Template::<Changes>::from_str(
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert!(template.settings.fragments_dir.is_none());
+    }
+
+    #[test]
+    fn template_revision_selection_parsing() {
+        let template = Template::<Changes>::from_str(
+            "\
+range: v1.0.0..v2.0.0
+latest: true
+since: 2024-01-01
+until: 2024-06-01
+commit-path: crates/mkchlog
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert_eq!(template.settings.range, Some("v1.0.0..v2.0.0".to_owned()));
+        assert!(template.settings.latest());
+        assert_eq!(template.settings.since, Some("2024-01-01".to_owned()));
+        assert_eq!(template.settings.until, Some("2024-06-01".to_owned()));
+        assert_eq!(
+            template.settings.commit_path,
+            Some(std::path::PathBuf::from("crates/mkchlog"))
+        );
+    }
+
+    #[test]
+    fn template_revision_selection_unset_by_default() {
+        let template = Template::<Changes>::from_str(
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        assert!(template.settings.range.is_none());
+        assert!(!template.settings.latest());
+        assert!(template.settings.since.is_none());
+        assert!(template.settings.until.is_none());
+        assert!(template.settings.commit_path.is_none());
+    }
+
+    #[test]
+    fn template_parse_layer_allows_missing_sections() {
+        let res = Template::<Changes>::parse_layer(
+            "git-path: /srv/repo\n",
+            std::path::Path::new(".mkchlog.yml"),
+        );
+        assert!(res.is_ok());
+
+        let mut template = res.unwrap();
+        assert_eq!(
+            template.settings.git_path,
+            Some(std::path::PathBuf::from("/srv/repo"))
+        );
+        assert!(template.data().is_empty());
+    }
+
+    /// Returns a fresh scratch directory under the OS temp dir for include tests to write fragment files into.
+    fn include_test_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "mkchlog_include_test_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn template_include_splices_fragment_sections() {
+        let dir = include_test_dir("splice");
+
+        std::fs::write(
+            dir.join("shared.yml"),
+            "\
+sections:
+    dev:
+        title: Development
+    features:
+        title: Should be overridden by the local definition
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join(".mkchlog.yml"),
+            "\
+sections:
+    include: shared.yml
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        let res = Template::<Changes>::from_path(&dir.join(".mkchlog.yml"));
+        assert!(res.is_ok());
+
+        let mut template = res.unwrap();
+        let data = template.data();
+
+        assert_eq!(data.keys().collect::<Vec<_>>(), vec!["dev", "features"]);
+        assert_eq!(data.get("dev").unwrap().title, "Development");
+        assert_eq!(data.get("features").unwrap().title, "New features");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn template_include_list_of_paths() {
+        let dir = include_test_dir("list");
+
+        std::fs::write(
+            dir.join("a.yml"),
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join("b.yml"),
+            "\
+sections:
+    dev:
+        title: Development
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join(".mkchlog.yml"),
+            "\
+sections:
+    include:
+        - a.yml
+        - b.yml
+",
+        )
+        .unwrap();
 
-    pub struct FileReaderMock {
-        content: Cursor<String>,
-    }
+        let res = Template::<Changes>::from_path(&dir.join(".mkchlog.yml"));
+        assert!(res.is_ok());
 
-    impl FileReaderMock {
-        pub fn new(content: &str) -> Self {
-            Self {
-                content: Cursor::new(content.to_owned()),
-            }
-        }
+        let mut template = res.unwrap();
+        let data = template.data();
+        assert_eq!(data.keys().collect::<Vec<_>>(), vec!["features", "dev"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
-    impl std::io::Read for FileReaderMock {
-        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-            self.content.read(buf)
-        }
+    #[test]
+    fn template_include_cycle_detected() {
+        let dir = include_test_dir("cycle");
+
+        std::fs::write(
+            dir.join("a.yml"),
+            "\
+sections:
+    include: b.yml
+",
+        )
+        .unwrap();
+
+        std::fs::write(
+            dir.join("b.yml"),
+            "\
+sections:
+    include: a.yml
+",
+        )
+        .unwrap();
+
+        let res = Template::<Changes>::from_path(&dir.join("a.yml"));
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("include cycle detected: a.yml -> b.yml -> a.yml"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
-    fn template_valid_yaml() {
-        use super::Section;
-        use indexmap::IndexMap;
+    fn template_include_in_subsection() {
+        let dir = include_test_dir("subsection");
 
-        let f = FileReaderMock::new(
+        std::fs::write(
+            dir.join("vuln_fixes.yml"),
             "\
-skip-commits-up-to: bc58e6bf2cf640d46aa832e297d0f215f76dfce0
+sections:
+    regressions:
+        title: Regressions
+",
+        )
+        .unwrap();
 
+        std::fs::write(
+            dir.join(".mkchlog.yml"),
+            "\
 sections:
-    # section identifier selected by project maintainer
     security:
-        # The header presented to the user
         title: Security
-        # desctiption is optional and will appear above changes
-        description: This section contains very important security-related changes.
         subsections:
+            include: vuln_fixes.yml
             vuln_fixes:
                 title: Fixed vulnerabilities
-    features:
-        # some comment
-        title: New features
-    bug_fixes:
-        title: Fixed bugs
-    breaking:
-        title: Breaking changes
-    perf:
-        title: Performance improvements
-    dev:
-        title: Development
-        description: Internal development changes
 ",
-        );
+        )
+        .unwrap();
 
-        let res = Template::new(f);
+        let res = Template::<Changes>::from_path(&dir.join(".mkchlog.yml"));
         assert!(res.is_ok());
 
         let mut template = res.unwrap();
-
-        // check for correctly parsed settings
-        let settings = &template.settings;
-
+        let security = template.data().get("security").unwrap();
         assert_eq!(
-            settings.skip_commits_up_to.as_ref().unwrap(),
-            "bc58e6bf2cf640d46aa832e297d0f215f76dfce0"
+            security.subsections.get("vuln_fixes").unwrap().title,
+            "Fixed vulnerabilities"
+        );
+        assert_eq!(
+            security.subsections.get("regressions").unwrap().title,
+            "Regressions"
         );
 
-        // check if parsed template has correct format
-        let template_data = template.data();
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
-        let exp_keys = template_data.keys().collect::<Vec<_>>();
-        assert_eq!(exp_keys.len(), 6);
+    #[test]
+    fn template_add_rename_remove_section() {
+        let mut template = Template::<Changes>::from_str(
+            "\
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        template.add_section("dev", "Development", "Internal development changes");
         assert_eq!(
-            exp_keys,
-            vec![
-                "security",
-                "features",
-                "bug_fixes",
-                "breaking",
-                "perf",
-                "dev",
-            ]
+            template.data().keys().collect::<Vec<_>>(),
+            vec!["features", "dev"]
         );
 
-        let exp_sections = template_data.values().cloned().collect::<Vec<_>>();
-        assert_eq!(exp_sections.len(), 6);
-
-        // 'security' section with subsection
-        let mut subsecs = IndexMap::new();
-        subsecs.insert(
-            "vuln_fixes".to_owned(),
-            Section {
-                title: "Fixed vulnerabilities".to_owned(),
-                description: "".to_owned(),
-                subsections: IndexMap::new(),
-                changes: Changes::default(),
-            },
+        template.rename_section("dev", "development").unwrap();
+        assert_eq!(
+            template.data().keys().collect::<Vec<_>>(),
+            vec!["features", "development"]
         );
         assert_eq!(
-            exp_sections[0],
-            Section {
-                title: "Security".to_owned(),
-                description: "This section contains very important security-related changes."
-                    .to_owned(),
-                subsections: subsecs,
-                changes: Changes::default(),
-            }
+            template.data().get("development").unwrap().title,
+            "Development"
         );
 
-        // 'features' section
+        let removed = template.remove_section("development");
+        assert!(removed.is_some());
+        assert_eq!(template.data().keys().collect::<Vec<_>>(), vec!["features"]);
+
+        assert!(template.rename_section("missing", "whatever").is_err());
+    }
+
+    #[test]
+    fn template_section_mut_and_subsection_mut() {
+        let mut template = Template::<Changes>::from_str(
+            "\
+sections:
+    security:
+        title: Security
+        subsections:
+            vuln_fixes:
+                title: Fixed vulnerabilities
+",
+        )
+        .unwrap();
+
+        template.section_mut("security").unwrap().title = "Security fixes".to_owned();
         assert_eq!(
-            exp_sections[1],
-            Section {
-                title: "New features".to_owned(),
-                description: "".to_owned(),
-                subsections: IndexMap::new(),
-                changes: Changes::default(),
-            }
+            template.data().get("security").unwrap().title,
+            "Security fixes"
         );
 
-        // 'dev' section
+        template
+            .section_mut("security")
+            .unwrap()
+            .add_subsection("regressions", "Regressions", "");
+        assert!(template.subsection_mut("security", "regressions").is_some());
+
+        let removed = template
+            .section_mut("security")
+            .unwrap()
+            .remove_subsection("vuln_fixes");
+        assert!(removed.is_some());
+        assert!(template.subsection_mut("security", "vuln_fixes").is_none());
+
+        assert!(template.subsection_mut("missing", "whatever").is_none());
+    }
+
+    #[test]
+    fn template_to_yaml_round_trip() {
+        let mut template = Template::<Changes>::from_str(
+            "\
+skip-commits-up-to: aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa
+sections:
+    security:
+        title: Security
+        description: Security related changes.
+        subsections:
+            vuln_fixes:
+                title: Fixed vulnerabilities
+    features:
+        title: New features
+",
+        )
+        .unwrap();
+
+        let yaml = template.to_yaml();
+        assert_eq!(yaml, template.to_string());
+
+        let mut reparsed = Template::<Changes>::from_str(&yaml).unwrap();
+
         assert_eq!(
-            exp_sections[5],
-            Section {
-                title: "Development".to_owned(),
-                description: "Internal development changes".to_owned(),
-                subsections: IndexMap::new(),
-                changes: Changes::default(),
-            }
+            reparsed.settings.skip_commits_up_to,
+            template.settings.skip_commits_up_to
         );
+        assert_eq!(reparsed.data(), template.data());
     }
 
     #[test]
-    fn template_malformed_yaml() {
-        let f = FileReaderMock::new(
+    fn template_parses_match_block() {
+        let mut template = Template::<Changes>::from_str(
             "\
-    features: title: New features
-    perf:
-        title: Performance improvements",
+sections:
+    features:
+        title: New features
+        match:
+            types: [feat]
+            scopes: [api, cli]
+            regex: '^feat'
+    bug_fixes:
+        title: Fixed bugs
+        match:
+            types: [fix]
+",
+        )
+        .unwrap();
+
+        let features = &template.data()["features"];
+        assert_eq!(features.matcher.types, vec!["feat".to_owned()]);
+        assert_eq!(
+            features.matcher.scopes,
+            vec!["api".to_owned(), "cli".to_owned()]
         );
-        let res = Template::<Changes>::new(f);
+        assert!(features
+            .matcher
+            .regex
+            .as_ref()
+            .unwrap()
+            .is_match("feat: add thing"));
 
-        assert!(res.is_err());
-        assert!(res
-            .unwrap_err()
-            .to_string()
-            .starts_with("Error parsing config YAML file:"));
+        let bug_fixes = &template.data()["bug_fixes"];
+        assert_eq!(bug_fixes.matcher.types, vec!["fix".to_owned()]);
+        assert!(bug_fixes.matcher.scopes.is_empty());
+        assert!(bug_fixes.matcher.regex.is_none());
     }
 
     #[test]
-    fn template_missing_sections_key() {
-        let f = FileReaderMock::new(
+    fn template_section_without_match_has_empty_matcher() {
+        let mut template = Template::<Changes>::from_str(
             "\
-features:
-    title: New features
-perf:
-    title: Performance improvements",
-        );
-        let res = Template::<Changes>::new(f);
+sections:
+    features:
+        title: New features
+",
+        )
+        .unwrap();
 
-        assert!(res.is_err());
-        assert!(res
-            .unwrap_err()
-            .to_string()
-            .starts_with("Missing 'sections' key in config file"));
+        assert!(template.data()["features"].matcher.is_empty());
     }
 
     #[test]
-    fn template_misspelled_sections_key() {
-        let f = FileReaderMock::new(
+    fn template_invalid_match_regex() {
+        let res = Template::<Changes>::from_str(
             "\
-sekciones:
+sections:
     features:
         title: New features
-    perf:
-        title: Performance improvements",
+        match:
+            regex: '['
+",
         );
 
-        let res = Template::<Changes>::new(f);
         assert!(res.is_err());
-        assert_eq!(
-            res.unwrap_err().to_string(),
-            "Missing 'sections' key in config file"
-        );
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid 'match.regex' in section 'features':"));
     }
 
     #[test]
-    fn template_malformed_sections_key() {
-        let f = FileReaderMock::new(
+    fn template_rejects_duplicate_type_claims() {
+        let res = Template::<Changes>::from_str(
             "\
-sections: [whatever]
+sections:
+    features:
+        title: New features
+        match:
+            types: [feat]
+    enhancements:
+        title: Enhancements
+        match:
+            types: [feat]
 ",
         );
 
-        let res = Template::<Changes>::new(f);
         assert!(res.is_err());
         assert_eq!(
             res.unwrap_err().to_string(),
-            "Malformed 'sections' key in config file"
+            "section 'features' and 'enhancements' both match type 'feat'"
         );
     }
 
     #[test]
-    fn template_missing_title_in_section() {
-        let f = FileReaderMock::new(
+    fn template_classify_matches_type_scope_and_regex() {
+        let template = Template::<Changes>::from_str(
             "\
 sections:
     features:
-        description: New features
-    perf:
-        title: Performance improvements",
-        );
+        title: New features
+        match:
+            types: [feat]
+    bug_fixes:
+        title: Fixed bugs
+        match:
+            scopes: [core]
+    security:
+        title: Security
+        match:
+            regex: '^CVE'
+    dev:
+        title: Development
+",
+        )
+        .unwrap();
 
-        let res = Template::<Changes>::new(f);
-        assert!(res.is_err());
         assert_eq!(
-            res.unwrap_err().to_string(),
-            "Missing 'title' in section 'features' in config file"
+            template.classify(Some("feat"), None, "feat: add thing", false, None),
+            Some("features".to_owned())
+        );
+        assert_eq!(
+            template.classify(Some("fix"), Some("core"), "fix: patch core", false, None),
+            Some("bug_fixes".to_owned())
+        );
+        assert_eq!(
+            template.classify(None, None, "CVE-2024-0001 fixed", false, None),
+            Some("security".to_owned())
+        );
+        assert_eq!(
+            template.classify(Some("chore"), None, "chore: tidy", false, None),
+            None
+        );
+        assert_eq!(
+            template.classify(Some("chore"), None, "chore: tidy", false, Some("dev")),
+            Some("dev".to_owned())
+        );
+        assert_eq!(
+            template.classify(Some("chore"), None, "chore: tidy", false, Some("missing")),
+            None
         );
     }
 
     #[test]
-    fn template_invalid_title_in_section() {
-        let f = FileReaderMock::new(
+    fn template_classify_matches_breaking_change_ahead_of_type() {
+        let template = Template::<Changes>::from_str(
             "\
 sections:
     features:
         title: New features
-    perf:
-        title: [Performance improvements]",
-        );
+        match:
+            types: [feat]
+    breaking:
+        title: Breaking changes
+        match:
+            breaking: true
+",
+        )
+        .unwrap();
 
-        let res = Template::<Changes>::new(f);
-        assert!(res.is_err());
         assert_eq!(
-            res.unwrap_err().to_string(),
-            "Invalid 'title' in section 'perf' in config file"
+            template.classify(Some("feat"), None, "feat!: drop old API", true, None),
+            Some("breaking".to_owned())
+        );
+        assert_eq!(
+            template.classify(Some("feat"), None, "feat: add thing", false, None),
+            Some("features".to_owned())
         );
     }
 }
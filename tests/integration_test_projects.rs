@@ -5,6 +5,7 @@ mod mocks;
 use mkchlog::changelog;
 use mkchlog::changelog::Changelog;
 use mkchlog::config::Command;
+use mkchlog::config::OutputFormat;
 use mkchlog::git::Git;
 use mkchlog::template::Template;
 use mocks::GitCmdMock;
@@ -26,7 +27,7 @@ fn generate_changelog(
     let mut template = Template::<changelog::Changes>::new(f).unwrap();
     let mut changelog = Changelog::new(&mut template, git);
 
-    changelog.generate(project, COMMAND)
+    changelog.generate(project, COMMAND, OutputFormat::Markdown)
 }
 
 #[test]
@@ -688,7 +689,7 @@ Date:   Tue Jun 13 16:24:22 2023 +0200
     let mut template = Template::<changelog::Changes>::new(f).unwrap();
     let mut changelog = Changelog::new(&mut template, git);
 
-    let res = changelog.generate(None, Command::Check);
+    let res = changelog.generate(None, Command::Check, OutputFormat::Markdown);
 
     assert!(res.is_err());
     assert!(res
@@ -1,4 +1,4 @@
-use mkchlog::git::GitLogCommand;
+use mkchlog::git::{GitLogCommand, GitLogOutput};
 use std::error::Error;
 
 pub struct GitCmdMock {
@@ -12,7 +12,10 @@ impl GitCmdMock {
 }
 
 impl GitLogCommand for GitCmdMock {
-    fn get_log(&self) -> Result<String, Box<dyn Error>> {
-        Ok(self.log.to_string())
+    fn get_log(&self) -> Result<GitLogOutput, Box<dyn Error>> {
+        Ok(GitLogOutput {
+            log: self.log.to_string(),
+            tags: vec![],
+        })
     }
 }
@@ -3,59 +3,138 @@ use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub fn check(config: &str, git_callback: js_sys::Function) -> Result<(), JsValue> {
-    run(config, git_callback).map_err(|error| {
-        use std::fmt::Write;
-
-        let mut error_message = format!("Error: {}", error);
-        let mut source = error.source();
-        while let Some(error) = source {
-            write!(error_message, ": {}", error).expect("writing to string never fails");
-            source = error.source();
-        }
-        error_message.into()
-    })
+    run(config, git_callback).map_err(format_error)
+}
+
+/// Like [`check`], but on success returns the generated changelog as a structured JSON
+/// string (see [`mkchlog::config::OutputFormat::Json`]) instead of only pass/fail, so JS
+/// callers can work with the parsed releases/sections/commits directly.
+#[wasm_bindgen]
+pub fn generate_json(config: &str, git_callback: js_sys::Function) -> Result<String, JsValue> {
+    generate(config, git_callback).map_err(format_error)
+}
+
+fn format_error(error: Box<dyn std::error::Error>) -> JsValue {
+    use std::fmt::Write;
+
+    let mut error_message = format!("Error: {}", error);
+    let mut source = error.source();
+    while let Some(error) = source {
+        write!(error_message, ": {}", error).expect("writing to string never fails");
+        source = error.source();
+    }
+    error_message.into()
 }
 
 fn run(config: &str, git_callback: js_sys::Function) -> Result<(), Box<dyn std::error::Error>> {
     use mkchlog::changelog::Changelog;
     use mkchlog::changelog::Changes;
+    use mkchlog::config::{Command, OutputFormat};
     use mkchlog::template::Template;
 
     let mut template = Template::<Changes>::from_str(config)?;
 
-    let git_cmd = GitCmd {
-        callback: git_callback,
-        commit_id: template.settings.skip_commits_up_to.clone(),
-    };
+    let git_cmd = GitCmd::new(git_callback, &template.settings);
     let git_cmd = Box::new(git_cmd);
     let git = mkchlog::git::Git::new(git_cmd);
 
     let mut changelog = Changelog::new(&mut template, git);
 
-    changelog.generate()?;
+    changelog.generate(None, Command::Check, OutputFormat::Markdown)?;
     Ok(())
 }
 
+fn generate(config: &str, git_callback: js_sys::Function) -> Result<String, Box<dyn std::error::Error>> {
+    use mkchlog::changelog::Changelog;
+    use mkchlog::changelog::Changes;
+    use mkchlog::config::{Command, OutputFormat};
+    use mkchlog::template::Template;
+
+    let mut template = Template::<Changes>::from_str(config)?;
+
+    let git_cmd = GitCmd::new(git_callback, &template.settings);
+    let git_cmd = Box::new(git_cmd);
+    let git = mkchlog::git::Git::new(git_cmd);
+
+    let mut changelog = Changelog::new(&mut template, git);
+
+    changelog.generate(None, Command::Generate, OutputFormat::Json)
+}
+
 struct GitCmd {
     commit_id: Option<String>,
+    /// Mirrors [`mkchlog::git::command::GitLogCmd`]'s revision-selection options, built from
+    /// the template's `range`/`latest`/`since`/`until`/`commit-path` settings (there's no CLI
+    /// here, so these can only come from the template).
+    range: Option<String>,
+    latest: bool,
+    since: Option<String>,
+    until: Option<String>,
+    commit_path: Option<String>,
     callback: js_sys::Function,
 }
 
+impl GitCmd {
+    fn new(callback: js_sys::Function, settings: &mkchlog::template::Settings) -> Self {
+        Self {
+            commit_id: settings.skip_commits_up_to.clone(),
+            range: settings.range.clone(),
+            latest: settings.latest(),
+            since: settings.since.clone(),
+            until: settings.until.clone(),
+            commit_path: settings
+                .commit_path
+                .as_ref()
+                .map(|path| path.to_string_lossy().into_owned()),
+            callback,
+        }
+    }
+
+    /// Mirrors [`mkchlog::git::command::GitLogCmd`]'s revision argument: an explicit `range`
+    /// takes precedence, then `latest` is rejected (resolving the latest tag needs shelling
+    /// out to `git describe`, which the JS callback doesn't support), then `commit_id`.
+    fn revision(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if let Some(range) = &self.range {
+            return Ok(Some(range.clone()));
+        }
+
+        if self.latest {
+            return Err("'latest' is not supported from the WASM build".into());
+        }
+
+        Ok(self.commit_id.as_ref().map(|commit_id| format!("{}..HEAD", commit_id)))
+    }
+}
+
 impl mkchlog::git::GitLogCommand for GitCmd {
-    fn get_log(&self) -> Result<String, Box<dyn std::error::Error>> {
+    fn get_log(&self) -> Result<mkchlog::git::GitLogOutput, Box<dyn std::error::Error>> {
         let args = js_sys::Array::new();
         args.push(&JsValue::from("log"));
         args.push(&JsValue::from("--no-merges"));
-        if let Some(commit_id) = &self.commit_id {
-            args.push(&JsValue::from(format!("{}..HEAD", commit_id)));
+        if let Some(revision) = self.revision()? {
+            args.push(&JsValue::from(revision));
+        }
+        if let Some(since) = &self.since {
+            args.push(&JsValue::from(format!("--since={}", since)));
+        }
+        if let Some(until) = &self.until {
+            args.push(&JsValue::from(format!("--until={}", until)));
+        }
+        if let Some(commit_path) = &self.commit_path {
+            args.push(&JsValue::from("--"));
+            args.push(&JsValue::from(commit_path.as_str()));
         }
         let args = JsValue::from(args);
         let result = self
             .callback
             .call1(&JsValue::null(), &args)
             .map_err(|error| format!("{:?}", error))?;
-        result
+        let log = result
             .as_string()
-            .ok_or_else(|| "The value returned by closure is not a string".into())
+            .ok_or_else(|| "The value returned by closure is not a string")?;
+
+        // the JS callback only ever returns `git log` output; tag-aware release
+        // grouping isn't supported from the WASM build
+        Ok(mkchlog::git::GitLogOutput { log, tags: vec![] })
     }
 }